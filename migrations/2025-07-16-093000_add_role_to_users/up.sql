@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE users
+ADD COLUMN role TEXT NOT NULL DEFAULT 'reader'
+    CHECK (role IN ('reader', 'editor', 'moderator', 'admin'));
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS languages_symbol_key;
+DROP INDEX IF EXISTS countries_iso_alpha3_key;
+DROP INDEX IF EXISTS countries_iso_alpha2_key;
+
+ALTER TABLE countries
+DROP COLUMN iso_alpha2,
+DROP COLUMN iso_alpha3;
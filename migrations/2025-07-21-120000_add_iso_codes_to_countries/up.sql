@@ -0,0 +1,8 @@
+-- Your SQL goes here
+ALTER TABLE countries
+ADD COLUMN iso_alpha2 TEXT CHECK (iso_alpha2 ~ '^[A-Z]{2}$'),
+ADD COLUMN iso_alpha3 TEXT CHECK (iso_alpha3 ~ '^[A-Z]{3}$');
+
+CREATE UNIQUE INDEX countries_iso_alpha2_key ON countries (iso_alpha2);
+CREATE UNIQUE INDEX countries_iso_alpha3_key ON countries (iso_alpha3);
+CREATE UNIQUE INDEX languages_symbol_key ON languages (symbol);
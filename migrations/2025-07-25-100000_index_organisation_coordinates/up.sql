@@ -0,0 +1,2 @@
+-- Your SQL goes here
+CREATE INDEX organisations_latitude_longitude_idx ON organisations (latitude, longitude);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE countries
+DROP COLUMN geo_json_low,
+DROP COLUMN geo_json_medium;
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+-- Simplified copies of geo_json served for `detail=low|medium`. They are
+-- derived from geo_json whenever a country is written through the API, and
+-- backfilled for existing rows by country_loader.
+ALTER TABLE countries
+ADD COLUMN geo_json_low JSONB,
+ADD COLUMN geo_json_medium JSONB;
@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE countries_neighbours;
+
+ALTER TABLE countries
+DROP COLUMN centroid_latitude,
+DROP COLUMN centroid_longitude,
+DROP COLUMN bbox_min_latitude,
+DROP COLUMN bbox_min_longitude,
+DROP COLUMN bbox_max_latitude,
+DROP COLUMN bbox_max_longitude,
+DROP COLUMN area_km2;
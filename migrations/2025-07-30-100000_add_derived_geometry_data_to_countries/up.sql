@@ -0,0 +1,19 @@
+-- Your SQL goes here
+-- Measures derived from geo_json, kept in sync by the same code paths that
+-- precompute the simplified geometries. The bounding box uses
+-- bbox_min_longitude > bbox_max_longitude for shapes crossing the antimeridian.
+ALTER TABLE countries
+ADD COLUMN centroid_latitude DOUBLE PRECISION,
+ADD COLUMN centroid_longitude DOUBLE PRECISION,
+ADD COLUMN bbox_min_latitude DOUBLE PRECISION,
+ADD COLUMN bbox_min_longitude DOUBLE PRECISION,
+ADD COLUMN bbox_max_latitude DOUBLE PRECISION,
+ADD COLUMN bbox_max_longitude DOUBLE PRECISION,
+ADD COLUMN area_km2 DOUBLE PRECISION;
+
+-- Countries sharing a border; every pair is stored in both directions.
+CREATE TABLE countries_neighbours (
+    country_id UUID REFERENCES countries(id),
+    neighbour_id UUID REFERENCES countries(id),
+    PRIMARY KEY (country_id, neighbour_id)
+);
@@ -0,0 +1,12 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX organisations_location_earth_idx;
+
+DROP FUNCTION organisation_location(DOUBLE PRECISION, DOUBLE PRECISION);
+
+ALTER TABLE organisations
+DROP CONSTRAINT organisations_latitude_range,
+DROP CONSTRAINT organisations_longitude_range;
+
+ALTER TABLE organisations
+ALTER COLUMN latitude TYPE DECIMAL(10, 8),
+ALTER COLUMN longitude TYPE DECIMAL(10, 8);
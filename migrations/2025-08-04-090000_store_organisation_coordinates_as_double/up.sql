@@ -0,0 +1,29 @@
+-- Your SQL goes here
+-- DECIMAL(10, 8) only holds values below 100, which excludes most longitudes.
+ALTER TABLE organisations
+ALTER COLUMN latitude TYPE DOUBLE PRECISION,
+ALTER COLUMN longitude TYPE DOUBLE PRECISION;
+
+-- The old columns held anything up to 99.99999999 in either direction, so
+-- every stored longitude is in range but a latitude may not be. The
+-- latitude check is NOT VALID so that such rows cannot make the migration
+-- fail; run VALIDATE CONSTRAINT once they are cleaned up.
+ALTER TABLE organisations
+ADD CONSTRAINT organisations_latitude_range CHECK (latitude BETWEEN -90 AND 90) NOT VALID,
+ADD CONSTRAINT organisations_longitude_range CHECK (longitude BETWEEN -180 AND 180);
+
+-- Both extensions ship with PostgreSQL (contrib), unlike PostGIS.
+CREATE EXTENSION IF NOT EXISTS cube;
+CREATE EXTENSION IF NOT EXISTS earthdistance;
+
+-- ll_to_earth looks up cube() and earth() through the search_path, which
+-- pg_dump empties while restoring indexes. Indexing through a wrapper that
+-- pins the search_path keeps the index restorable; queries must use the
+-- same function for the index to apply.
+CREATE FUNCTION organisation_location(latitude DOUBLE PRECISION, longitude DOUBLE PRECISION)
+RETURNS public.earth
+LANGUAGE sql IMMUTABLE STRICT PARALLEL SAFE
+SET search_path = public
+AS $$ SELECT ll_to_earth(latitude, longitude) $$;
+
+CREATE INDEX organisations_location_earth_idx ON organisations USING gist (organisation_location(latitude, longitude));
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX countries_name_id_idx;
+DROP INDEX organisations_created_at_id_idx;
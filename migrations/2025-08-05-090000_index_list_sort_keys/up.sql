@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- Stable orders used by offset and cursor pagination of the list endpoints.
+CREATE INDEX organisations_created_at_id_idx ON organisations (created_at, id);
+CREATE INDEX countries_name_id_idx ON countries (name, id);
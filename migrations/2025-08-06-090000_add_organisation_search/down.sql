@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX organisations_name_trgm_idx;
+DROP INDEX organisations_search_vector_idx;
+
+ALTER TABLE organisations
+DROP COLUMN search_vector;
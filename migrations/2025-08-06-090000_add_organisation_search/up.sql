@@ -0,0 +1,18 @@
+-- Your SQL goes here
+-- Full-text search over organisations. The `simple` configuration does not
+-- stem, which suits names and addresses in many languages; matching is by
+-- word prefix instead. The column is not part of the Diesel schema and is
+-- only referenced from SQL fragments.
+ALTER TABLE organisations
+ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', coalesce(name, '')), 'A') ||
+    setweight(to_tsvector('simple', coalesce(address, '')), 'B') ||
+    setweight(to_tsvector('simple', coalesce(description, '')), 'C')
+) STORED;
+
+CREATE INDEX organisations_search_vector_idx ON organisations USING gin (search_vector);
+
+-- Trigram word similarity (`<%`) on the name catches misspelled queries.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX organisations_name_trgm_idx ON organisations USING gin (name gin_trgm_ops);
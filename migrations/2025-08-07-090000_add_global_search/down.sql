@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX chats_search_vector_idx;
+
+ALTER TABLE chats
+DROP COLUMN search_vector;
+
+DROP INDEX countries_search_vector_idx;
+
+ALTER TABLE countries
+DROP COLUMN search_vector;
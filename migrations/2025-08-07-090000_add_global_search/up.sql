@@ -0,0 +1,23 @@
+-- Your SQL goes here
+-- Search vectors for the global search, built like the one on
+-- organisations. Country connections are searched through the names of
+-- their organisations and location country, so their document is built at
+-- query time instead.
+ALTER TABLE countries
+ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', coalesce(name, '')), 'A') ||
+    setweight(to_tsvector('simple', coalesce(iso_alpha2, '') || ' ' || coalesce(iso_alpha3, '')), 'A') ||
+    setweight(to_tsvector('simple', coalesce(capital_city, '')), 'B') ||
+    setweight(to_tsvector('simple', coalesce(description, '')), 'C')
+) STORED;
+
+CREATE INDEX countries_search_vector_idx ON countries USING gin (search_vector);
+
+ALTER TABLE chats
+ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', coalesce(app, '')), 'A') ||
+    setweight(to_tsvector('simple', coalesce(info, '')), 'B') ||
+    setweight(to_tsvector('simple', coalesce(link, '')), 'C')
+) STORED;
+
+CREATE INDEX chats_search_vector_idx ON chats USING gin (search_vector);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX chats_created_at_id_idx;
+
+ALTER TABLE chats
+DROP COLUMN created_at;
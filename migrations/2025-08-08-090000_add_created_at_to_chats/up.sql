@@ -0,0 +1,7 @@
+-- Your SQL goes here
+-- Chats are listed in creation order like organisations; existing chats
+-- get the time of the migration.
+ALTER TABLE chats
+ADD COLUMN created_at TIMESTAMP NOT NULL DEFAULT NOW();
+
+CREATE INDEX chats_created_at_id_idx ON chats (created_at, id);
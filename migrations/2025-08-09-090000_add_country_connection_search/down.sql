@@ -0,0 +1,24 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX chats_app_trgm_idx;
+
+DROP INDEX countries_name_trgm_idx;
+
+DROP INDEX countries_connections_search_name_trgm_idx;
+
+DROP INDEX countries_connections_search_vector_idx;
+
+DROP TRIGGER countries_connections_search_name ON countries;
+
+DROP TRIGGER organisations_connections_search_name ON organisations;
+
+DROP FUNCTION countries_connections_refresh_search_name();
+
+DROP TRIGGER countries_connections_search_name ON countries_connections;
+
+DROP FUNCTION countries_connections_set_search_name();
+
+ALTER TABLE countries_connections
+DROP COLUMN search_vector,
+DROP COLUMN search_name;
+
+DROP FUNCTION countries_connection_search_name(uuid, uuid, uuid);
@@ -0,0 +1,68 @@
+-- Your SQL goes here
+-- Country connections are searched through the names of their embassy,
+-- consulate and location country. The names are copied into `search_name`
+-- by triggers, so the document can be a stored column and both it and the
+-- trigram match are answered by indexes. Like `search_vector`, the columns
+-- are not part of the Diesel schema.
+CREATE FUNCTION countries_connection_search_name(embassy_id uuid, consulate_id uuid, country_id uuid)
+RETURNS text
+LANGUAGE sql STABLE AS $$
+    SELECT concat_ws(' ',
+        (SELECT name FROM organisations WHERE id = embassy_id),
+        (SELECT name FROM organisations WHERE id = consulate_id),
+        (SELECT name FROM countries WHERE id = country_id))
+$$;
+
+ALTER TABLE countries_connections
+ADD COLUMN search_name text NOT NULL DEFAULT '';
+
+UPDATE countries_connections
+SET search_name = countries_connection_search_name(embassy_org_id, consulate_org_id, location_country_id);
+
+ALTER TABLE countries_connections
+ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', search_name), 'A') ||
+    setweight(to_tsvector('simple', coalesce(common_info, '')), 'C')
+) STORED;
+
+CREATE FUNCTION countries_connections_set_search_name() RETURNS trigger
+LANGUAGE plpgsql AS $$
+BEGIN
+    NEW.search_name := countries_connection_search_name(NEW.embassy_org_id, NEW.consulate_org_id, NEW.location_country_id);
+    RETURN NEW;
+END
+$$;
+
+CREATE TRIGGER countries_connections_search_name
+BEFORE INSERT OR UPDATE OF embassy_org_id, consulate_org_id, location_country_id ON countries_connections
+FOR EACH ROW EXECUTE FUNCTION countries_connections_set_search_name();
+
+-- Renaming an organisation or country renames the connections using it.
+CREATE FUNCTION countries_connections_refresh_search_name() RETURNS trigger
+LANGUAGE plpgsql AS $$
+BEGIN
+    UPDATE countries_connections
+    SET search_name = countries_connection_search_name(embassy_org_id, consulate_org_id, location_country_id)
+    WHERE embassy_org_id = NEW.id OR consulate_org_id = NEW.id OR location_country_id = NEW.id;
+    RETURN NULL;
+END
+$$;
+
+CREATE TRIGGER organisations_connections_search_name
+AFTER UPDATE OF name ON organisations
+FOR EACH ROW WHEN (OLD.name IS DISTINCT FROM NEW.name)
+EXECUTE FUNCTION countries_connections_refresh_search_name();
+
+CREATE TRIGGER countries_connections_search_name
+AFTER UPDATE OF name ON countries
+FOR EACH ROW WHEN (OLD.name IS DISTINCT FROM NEW.name)
+EXECUTE FUNCTION countries_connections_refresh_search_name();
+
+CREATE INDEX countries_connections_search_vector_idx ON countries_connections USING gin (search_vector);
+
+CREATE INDEX countries_connections_search_name_trgm_idx ON countries_connections USING gin (search_name gin_trgm_ops);
+
+-- The other kinds of the global search match these names by similarity.
+CREATE INDEX countries_name_trgm_idx ON countries USING gin (name gin_trgm_ops);
+
+CREATE INDEX chats_app_trgm_idx ON chats USING gin ((coalesce(app, '')) gin_trgm_ops);
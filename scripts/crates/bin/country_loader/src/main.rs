@@ -1,15 +1,23 @@
+use std::collections::{HashMap, HashSet};
+use std::env;
 use std::error::Error;
+use std::fmt;
 use std::fs::File;
 use std::io::BufReader;
+use diesel::prelude::*;
 use dotenv::dotenv;
 use serde_json::json;
-use landly_server::data::models::{Country, CreateCountry};
+use uuid::Uuid;
+use landly_server::data::models::{Country, CountryNeighbour, CountryToLanguage, CreateCountry, CreateLanguage, Language, UpdateCountry};
+use landly_server::error::AppError;
 use landly_server::utils::db::establish_connection;
 use country_parser::MergedCountry;
 
 fn main() -> Result<(), Box<dyn Error>> {
     dotenv().ok();
 
+    let dry_run = env::args().skip(1).any(|arg| arg == "--dry-run");
+
     let db_pool = establish_connection();
 
     let file = File::open("./bin/country_parser/data/merged_countries.json")?;
@@ -17,26 +25,209 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     let countries: Vec<MergedCountry> = serde_json::from_reader(reader)?;
 
-    println!("Loading of {} countries to DB", countries.len());
+    println!("Loading of {} countries to DB{}", countries.len(), if dry_run { " (dry run)" } else { "" });
 
     let connection = &mut db_pool.get()?;
 
+    // Everything runs in one transaction: any failure leaves the database
+    // untouched, and a dry run rolls back after computing the summary.
+    let result = connection.transaction::<_, LoadError, _>(|conn| {
+        let summary = load_countries(conn, countries)?;
+
+        if dry_run {
+            return Err(LoadError::DryRun(summary));
+        }
+
+        Ok(summary)
+    });
+
+    match result {
+        Ok(summary) => {
+            println!("{}", summary);
+            println!("Loading completed successfully!");
+        }
+        Err(LoadError::DryRun(summary)) => {
+            println!("{}", summary);
+            println!("Dry run finished, no changes were written");
+        }
+        Err(LoadError::App(e)) => {
+            eprintln!("Loading failed, all changes were rolled back");
+            return Err(e.into());
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct LoadSummary {
+    inserted: usize,
+    updated: usize,
+    unchanged: usize,
+    languages_created: usize,
+    geometries_derived: usize,
+    neighbour_pairs: usize,
+}
+
+impl fmt::Display for LoadSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Countries inserted: {}, updated: {}, unchanged: {}; languages created: {}; geometries derived: {}; neighbour pairs: {}",
+            self.inserted, self.updated, self.unchanged, self.languages_created, self.geometries_derived, self.neighbour_pairs
+        )
+    }
+}
+
+enum LoadError {
+    /// Sentinel used to roll back the transaction of a dry run.
+    DryRun(LoadSummary),
+    App(AppError),
+}
+
+impl From<AppError> for LoadError {
+    fn from(e: AppError) -> Self {
+        LoadError::App(e)
+    }
+}
+
+impl From<diesel::result::Error> for LoadError {
+    fn from(e: diesel::result::Error) -> Self {
+        LoadError::App(e.into())
+    }
+}
+
+fn load_countries(
+    conn: &mut PgConnection,
+    countries: Vec<MergedCountry>,
+) -> Result<LoadSummary, LoadError> {
+    let mut summary = LoadSummary::default();
+
+    // Language ids by ISO 639-3 code, so every language is looked up or created once.
+    let mut language_ids: HashMap<String, Uuid> = HashMap::new();
+
     for country_data in countries {
-        let country = CreateCountry {
-            name: country_data.name,
-            geo_json: Some(json!(country_data.geo_json)),
-            flag: Some(country_data.flag),
-            capital_city: country_data.capital,
-            description: None,
+        let geo_json = Some(json!(country_data.geo_json));
+        let flag = Some(country_data.flag);
+        let iso_alpha2 = Some(country_data.iso_alpha2);
+
+        let (country, inserted, changed) = match Country::find_by_import_key(conn, &country_data.iso_alpha3, &country_data.name)? {
+            Some(existing) => {
+                let changed = existing.name != country_data.name
+                    || existing.geo_json != geo_json
+                    || existing.flag != flag
+                    || existing.capital_city != country_data.capital
+                    || existing.iso_alpha2 != iso_alpha2
+                    || existing.iso_alpha3.as_deref() != Some(country_data.iso_alpha3.as_str());
+
+                if changed {
+                    let updated = Country::update(
+                        conn,
+                        existing.id,
+                        &UpdateCountry {
+                            name: Some(country_data.name),
+                            geo_json,
+                            flag,
+                            capital_city: country_data.capital,
+                            description: None,
+                            iso_alpha2,
+                            iso_alpha3: Some(country_data.iso_alpha3),
+                        },
+                    )?;
+                    (updated, false, true)
+                } else {
+                    (existing, false, false)
+                }
+            }
+            None => {
+                let created = Country::create(
+                    conn,
+                    &CreateCountry {
+                        name: country_data.name,
+                        geo_json,
+                        flag,
+                        capital_city: country_data.capital,
+                        description: None,
+                        iso_alpha2,
+                        iso_alpha3: Some(country_data.iso_alpha3),
+                    },
+                )?;
+                (created, true, true)
+            }
         };
 
-        match Country::create(connection, &country) {
-            Ok(_) => println!("Country added: {}", country.name),
-            Err(e) => eprintln!("Error {}: {}", country.name, e),
+        let languages_linked = link_languages(conn, &country, country_data.languages, &mut language_ids, &mut summary)?;
+
+        if inserted {
+            summary.inserted += 1;
+            println!("Country added: {}", country.name);
+        } else if changed || languages_linked {
+            summary.updated += 1;
+            println!("Country updated: {}", country.name);
+        } else {
+            summary.unchanged += 1;
         }
     }
 
-    println!("Loading completed successfully!");
+    // Inserted and updated countries get their derived geometry data on write;
+    // this covers rows stored before it was computed.
+    summary.geometries_derived = Country::backfill_derived_geometries(conn)?;
+    // Borders are compared pairwise once all shapes are in place.
+    summary.neighbour_pairs = CountryNeighbour::rebuild_all(conn)?;
 
-    Ok(())
+    Ok(summary)
+}
+
+/// Links the country to its languages, creating missing languages on the way.
+/// Existing links are kept; returns whether any new link was added.
+fn link_languages(
+    conn: &mut PgConnection,
+    country: &Country,
+    languages: impl IntoIterator<Item = (String, String)>,
+    language_ids: &mut HashMap<String, Uuid>,
+    summary: &mut LoadSummary,
+) -> Result<bool, LoadError> {
+    let linked: HashSet<Uuid> = Language::get_by_country_ids(conn, &[country.id])?
+        .into_iter()
+        .map(|(_, language)| language.id)
+        .collect();
+    let mut linked_any = false;
+
+    for (code, name) in languages {
+        let language_id = match language_ids.get(&code) {
+            Some(id) => *id,
+            None => {
+                let language = match Language::get_by_symbol(conn, &code)? {
+                    Some(language) => language,
+                    None => {
+                        summary.languages_created += 1;
+                        Language::create(
+                            conn,
+                            &CreateLanguage {
+                                name,
+                                symbol: Some(code.clone()),
+                            },
+                        )?
+                    }
+                };
+                language_ids.insert(code, language.id);
+                language.id
+            }
+        };
+
+        if linked.contains(&language_id) {
+            continue;
+        }
+
+        CountryToLanguage::attach(
+            conn,
+            &CountryToLanguage {
+                country_id: country.id,
+                language_id,
+            },
+        )?;
+        linked_any = true;
+    }
+
+    Ok(linked_any)
 }
@@ -1,9 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use landly_server::utils::topojson::{build_topology, validate_quantization, TopologyFeature, MAX_QUANTIZATION};
 use serde::{Deserialize, Serialize};
-use serde_json::{from_reader, to_writer_pretty, Value};
+use serde_json::{from_reader, json, to_writer, to_writer_pretty, Value};
 
 #[derive(Debug, Deserialize)]
 pub struct GeoJson {
@@ -69,12 +71,44 @@ pub struct Name {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MergedCountry {
     pub name: String,
+    pub iso_alpha2: String,
+    pub iso_alpha3: String,
     pub capital: Option<String>,
     pub flag: String,
+    /// Spoken languages keyed by their ISO 639-3 code, e.g. `"deu": "German"`.
+    pub languages: BTreeMap<String, String>,
+    /// Links to the country on map services, e.g. `"openStreetMaps": "https://..."`.
+    pub maps: BTreeMap<String, String>,
     pub geo_json: Geometry,
 }
 
+/// Command line options: `--topojson` additionally writes the borders as a
+/// TopoJSON topology, snapped to a grid of `--quantization=<steps>` per axis.
+struct Options {
+    topojson: bool,
+    quantization: Option<u32>,
+}
+
+impl Options {
+    fn from_args() -> Result<Self> {
+        let mut options = Options { topojson: false, quantization: None };
+        for arg in env::args().skip(1) {
+            match arg.split_once('=') {
+                None if arg == "--topojson" => options.topojson = true,
+                Some(("--quantization", steps)) => {
+                    options.quantization = Some(steps.parse().map_err(|_| anyhow!("Invalid quantization '{}'", steps))?);
+                }
+                _ => return Err(anyhow!("Unknown argument '{}'", arg)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
 fn main() -> Result<()> {
+    let options = Options::from_args()?;
+
     // 1. Reading countries.json
     let countries_file = File::open("./data/countries.json")?;
     let countries: Vec<CountryInfo> = from_reader(BufReader::new(countries_file))?;
@@ -99,8 +133,12 @@ fn main() -> Result<()> {
         if let Some(geo) = geo_map.get(&key2).or_else(|| geo_map.get(&key3)) {
             merged.push(MergedCountry {
                 name: country.name.common,
+                iso_alpha2: key2,
+                iso_alpha3: key3,
                 capital: country.capital.and_then(|v| v.into_iter().next()),
                 flag: country.flag,
+                languages: country.languages.into_iter().collect(),
+                maps: country.maps.into_iter().collect(),
                 geo_json: geo.geometry.clone(),
             });
         }
@@ -111,5 +149,27 @@ fn main() -> Result<()> {
     to_writer_pretty(BufWriter::new(output), &merged)?;
     println!("Merged {} countries", merged.len());
 
+    // 6. Optionally saving the borders as TopoJSON
+    if options.topojson {
+        let quantization = validate_quantization(options.quantization)
+            .map_err(|_| anyhow!("Quantization must be between 2 and {}", MAX_QUANTIZATION))?;
+        let features: Vec<TopologyFeature> = merged
+            .iter()
+            .map(|country| TopologyFeature {
+                id: Some(country.iso_alpha3.clone()),
+                properties: json!({
+                    "name": country.name,
+                    "isoAlpha2": country.iso_alpha2,
+                    "isoAlpha3": country.iso_alpha3,
+                }),
+                geometry: json!(country.geo_json),
+            })
+            .collect();
+
+        let output = File::create("./data/countries.topojson")?;
+        to_writer(BufWriter::new(output), &build_topology("countries", &features, quantization))?;
+        println!("Saved TopoJSON of {} countries with quantization {}", features.len(), quantization);
+    }
+
     Ok(())
 }
@@ -0,0 +1,40 @@
+//! Grants the admin role to an already registered user:
+//!
+//!     cargo run -p promote_admin -- admin@example.com
+//!
+//! Registration only ever creates readers, so this is how the first admin
+//! of a deployment is made; later admins can be promoted through the API.
+
+use std::env;
+use std::error::Error;
+use diesel::dsl::now;
+use diesel::prelude::*;
+use dotenv::dotenv;
+use landly_server::data::schema::users;
+use landly_server::utils::db::establish_connection;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    dotenv().ok();
+
+    let Some(email) = env::args().nth(1).map(|email| email.trim().to_lowercase()) else {
+        return Err("usage: promote_admin <email>".into());
+    };
+
+    let db_pool = establish_connection();
+    let connection = &mut db_pool.get()?;
+
+    let promoted = diesel::update(users::table.filter(users::email.eq(&email)))
+        .set((
+            users::role.eq("admin"),
+            users::updated_at.eq(now),
+        ))
+        .execute(connection)?;
+
+    if promoted == 0 {
+        return Err(format!("no user is registered with the email {}", email).into());
+    }
+
+    println!("{} is now an admin", email);
+
+    Ok(())
+}
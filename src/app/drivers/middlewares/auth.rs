@@ -0,0 +1,40 @@
+use crate::app::features::auth::permissions::Role;
+use crate::error::AppError;
+use crate::utils::jwt::{verify_token, TokenKind};
+use actix_web::{dev::Payload, http::header, FromRequest, HttpRequest};
+use serde_json::json;
+use std::future::{ready, Ready};
+use uuid::Uuid;
+
+/// Caller identified by a valid `Authorization: Bearer <access token>` header.
+/// Add it as a controller argument to make the route require authentication;
+/// what the caller may do is decided by the usecases via `permissions::authorize`.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub id: Uuid,
+    pub role: Role,
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(authenticate(req))
+    }
+}
+
+fn authenticate(req: &HttpRequest) -> Result<AuthenticatedUser, AppError> {
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| AppError::Unauthorized(json!({ "error": "Missing bearer token" })))?;
+    let claims = verify_token(token.trim(), TokenKind::Access)?;
+
+    Ok(AuthenticatedUser {
+        id: claims.sub,
+        role: claims.role,
+    })
+}
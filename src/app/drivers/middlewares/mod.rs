@@ -1,2 +1,3 @@
 pub mod cors;
 pub mod state;
+pub mod auth;
\ No newline at end of file
@@ -0,0 +1,20 @@
+use super::controllers::{list_users, login, logout, refresh, register, update_user_role};
+use actix_web::{web, web::ServiceConfig};
+
+pub fn configure_services(cfg: &mut ServiceConfig) {
+    cfg.service(
+        web::scope("/auth")
+            .route("/register", web::post()
+                .to(register))
+            .route("/login", web::post()
+                .to(login))
+            .route("/refresh", web::post()
+                .to(refresh))
+            .route("/logout", web::post()
+                .to(logout))
+            .route("/users/list", web::get()
+                .to(list_users))
+            .route("/users/update-role/{id}", web::put()
+                .to(update_user_role))
+    );
+}
@@ -0,0 +1,189 @@
+use super::{
+    requests::{LoginRequest, RefreshTokenRequest, RegisterRequest, UpdateUserRoleRequest, UsersListQueryParams},
+    usecases::{FetchUsersUsecaseInput, LoginUsecaseInput, RefreshTokenUsecaseInput, RegisterUsecaseInput},
+};
+use crate::app::drivers::middlewares::{auth::AuthenticatedUser, state::AppState};
+use crate::error::AppError;
+use crate::utils::pagination::{PageLinks, PageRequest};
+use actix_web::{web::{Data, Json, Path, Query}, HttpRequest, HttpResponse};
+use uuid::Uuid;
+
+#[utoipa::path(
+    post,
+    path = "/auth/register",
+    context_path = "/api",
+    request_body = RegisterRequest,
+    responses(
+        (status = 200, description = "User registered successfully", body = super::presenters::UserContent),
+        (status = 422, description = "Invalid email, weak password or email already taken", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Auth"
+)]
+pub async fn register(
+    state: Data<AppState>,
+    form: Json<RegisterRequest>
+) -> Result<HttpResponse, AppError> {
+    let form = form.into_inner();
+
+    state
+        .di_container
+        .auth_usecase
+        .register(
+            RegisterUsecaseInput {
+                email: form.email,
+                password: form.password,
+            }
+        )
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    context_path = "/api",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Access and refresh tokens issued", body = super::presenters::TokenPairContent),
+        (status = 401, description = "Invalid credentials", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Auth"
+)]
+pub async fn login(
+    state: Data<AppState>,
+    form: Json<LoginRequest>
+) -> Result<HttpResponse, AppError> {
+    let form = form.into_inner();
+
+    state
+        .di_container
+        .auth_usecase
+        .login(
+            LoginUsecaseInput {
+                email: form.email,
+                password: form.password,
+            }
+        )
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    context_path = "/api",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "New access and refresh tokens issued", body = super::presenters::TokenPairContent),
+        (status = 401, description = "Refresh token is invalid, expired or revoked", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Auth"
+)]
+pub async fn refresh(
+    state: Data<AppState>,
+    form: Json<RefreshTokenRequest>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .auth_usecase
+        .refresh(
+            RefreshTokenUsecaseInput {
+                refresh_token: form.into_inner().refresh_token,
+            }
+        )
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    context_path = "/api",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "Refresh token revoked"),
+        (status = 401, description = "Refresh token is invalid or expired", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Auth"
+)]
+pub async fn logout(
+    state: Data<AppState>,
+    form: Json<RefreshTokenRequest>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .auth_usecase
+        .logout(
+            RefreshTokenUsecaseInput {
+                refresh_token: form.into_inner().refresh_token,
+            }
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/auth/users/list",
+    context_path = "/api",
+    params(UsersListQueryParams),
+    responses(
+        (status = 200, description = "Users list response, in registration order", body = super::presenters::MultipleUsersResponse, headers(
+            ("Link" = String, description = "RFC 8288 `first`, `prev`, `next` and `last` page links")
+        )),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Caller is not an admin", body = AppError),
+        (status = 422, description = "Invalid cursor or page size out of range", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Auth"
+)]
+pub async fn list_users(
+    state: Data<AppState>,
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    query: Query<UsersListQueryParams>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .auth_usecase
+        .fetch_users(
+            &user,
+            FetchUsersUsecaseInput {
+                page: PageRequest::parse(query.limit, query.offset, query.cursor.as_deref())?,
+                links: PageLinks::from_request(&req),
+            }
+        )
+}
+
+/// Change a user's role.
+///
+/// Access tokens carry the role they were issued with, so the user keeps
+/// the old role until their current access token expires, at most
+/// `ACCESS_TOKEN_TTL_MINUTES` (15) minutes. Tokens issued on refresh or
+/// login already carry the new role.
+#[utoipa::path(
+    put,
+    path = "/auth/users/update-role/{id}",
+    context_path = "/api",
+    request_body = UpdateUserRoleRequest,
+    params(
+        ("id" = Uuid, Path, description = "User ID to update")
+    ),
+    responses(
+        (status = 200, description = "User role updated successfully", body = super::presenters::UserContent),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Caller is not an admin", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Auth"
+)]
+pub async fn update_user_role(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    form: Json<UpdateUserRoleRequest>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .auth_usecase
+        .update_user_role(&user, id.into_inner(), form.role)
+}
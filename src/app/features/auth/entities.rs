@@ -0,0 +1,165 @@
+use super::permissions::Role;
+use crate::data::schema::{refresh_tokens, users};
+use crate::error::*;
+use crate::utils::pagination::{PageRequest, CURSOR_TIMESTAMP_FORMAT};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+#[derive(Debug, Serialize, Deserialize, Queryable, Insertable, Selectable, Clone)]
+#[diesel(table_name = users)]
+pub struct User {
+    pub id: Uuid,
+    pub email: String,
+    pub password_hash: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub role: Role,
+}
+
+impl User {
+    pub fn create(
+        conn: &mut PgConnection,
+        record: &CreateUser,
+    ) -> Result<Self, AppError> {
+        let result = diesel::insert_into(users::table)
+            .values(record)
+            .get_result::<User>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn fetch_by_id(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+    ) -> Result<Self, AppError> {
+        let result = users::table
+            .find(user_id)
+            .get_result::<User>(conn)?;
+
+        Ok(result)
+    }
+
+    /// One page of all users in registration order, with the id as
+    /// tie-breaker.
+    pub fn fetch_all(
+        conn: &mut PgConnection,
+        page: &PageRequest,
+    ) -> Result<Vec<Self>, AppError> {
+        let mut query = users::table
+            .order((users::created_at.asc(), users::id.asc()))
+            .limit(page.fetch_limit())
+            .into_boxed();
+        query = match page {
+            PageRequest::Offset { offset, .. } => query.offset(*offset),
+            PageRequest::Cursor { after, .. } => {
+                let created_at = after.parse_key(|key| NaiveDateTime::parse_from_str(key, CURSOR_TIMESTAMP_FORMAT).ok())?;
+                query.filter(
+                    users::created_at.gt(created_at)
+                        .or(users::created_at.eq(created_at).and(users::id.gt(after.id))),
+                )
+            }
+        };
+        let result = query.load::<User>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn count(
+        conn: &mut PgConnection,
+    ) -> Result<i64, AppError> {
+        let result = users::table
+            .count()
+            .get_result::<i64>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn update_role(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        role: Role,
+    ) -> Result<Self, AppError> {
+        let u = users::table
+            .find(user_id);
+        let result = diesel::update(u)
+            .set((
+                users::role.eq(role),
+                users::updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .get_result::<User>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn fetch_by_email(
+        conn: &mut PgConnection,
+        email: &str,
+    ) -> Result<Option<Self>, AppError> {
+        let result = users::table
+            .filter(users::email.eq(email))
+            .get_result::<User>(conn)
+            .optional()?;
+
+        Ok(result)
+    }
+}
+
+#[derive(Insertable, Clone)]
+#[diesel(table_name = users)]
+pub struct CreateUser {
+    pub email: String,
+    pub password_hash: String,
+    pub role: Role,
+}
+
+#[derive(Debug, Queryable, Insertable, Selectable, Clone)]
+#[diesel(table_name = refresh_tokens)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub expires_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl RefreshToken {
+    pub fn create(
+        conn: &mut PgConnection,
+        record: &CreateRefreshToken,
+    ) -> Result<Self, AppError> {
+        let result = diesel::insert_into(refresh_tokens::table)
+            .values(record)
+            .get_result::<RefreshToken>(conn)?;
+
+        Ok(result)
+    }
+
+    /// Revokes a token of the user unless it already is, in one statement,
+    /// and returns the number of tokens revoked: 0 means the token was
+    /// unknown or already used, e.g. by a concurrent request.
+    pub fn revoke(
+        conn: &mut PgConnection,
+        token_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<usize, AppError> {
+        let t = refresh_tokens::table
+            .find(token_id)
+            .filter(refresh_tokens::user_id.eq(user_id))
+            .filter(refresh_tokens::revoked_at.is_null());
+        let result = diesel::update(t)
+            .set(refresh_tokens::revoked_at.eq(chrono::Utc::now().naive_utc()))
+            .execute(conn)?;
+
+        Ok(result)
+    }
+}
+
+#[derive(Insertable, Clone)]
+#[diesel(table_name = refresh_tokens)]
+pub struct CreateRefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub expires_at: NaiveDateTime,
+}
@@ -0,0 +1,8 @@
+pub mod entities;
+pub mod presenters;
+pub mod controllers;
+pub mod repositories;
+pub mod usecases;
+pub mod requests;
+pub mod config;
+pub mod permissions;
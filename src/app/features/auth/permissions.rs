@@ -0,0 +1,183 @@
+use crate::app::drivers::middlewares::auth::AuthenticatedUser;
+use crate::error::AppError;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// User roles, ordered from least to most privileged. Every role is granted
+/// the permissions of the roles below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Reader,
+    Editor,
+    Moderator,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Reader => "reader",
+            Role::Editor => "editor",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "reader" => Ok(Role::Reader),
+            "editor" => Ok(Role::Editor),
+            "moderator" => Ok(Role::Moderator),
+            "admin" => Ok(Role::Admin),
+            _ => Err(AppError::UnprocessableEntity(json!({
+                "error": format!("Unknown role '{}'", value)
+            }))),
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for Role {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(serialize::IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Role {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        Role::from_str(&value).map_err(|e| e.to_string().into())
+    }
+}
+
+/// Operations guarded by the permission layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    CreateOrganisation,
+    UpdateOrganisation,
+    DeleteOrganisation,
+    CreateCountryConnection,
+    UpdateCountryConnection,
+    DeleteCountryConnection,
+    CreateChat,
+    UpdateChat,
+    DeleteChat,
+    ManageReferenceData,
+    ManageUsers,
+}
+
+impl Action {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::CreateOrganisation => "organisation:create",
+            Action::UpdateOrganisation => "organisation:update",
+            Action::DeleteOrganisation => "organisation:delete",
+            Action::CreateCountryConnection => "country_connection:create",
+            Action::UpdateCountryConnection => "country_connection:update",
+            Action::DeleteCountryConnection => "country_connection:delete",
+            Action::CreateChat => "chat:create",
+            Action::UpdateChat => "chat:update",
+            Action::DeleteChat => "chat:delete",
+            Action::ManageReferenceData => "reference_data:manage",
+            Action::ManageUsers => "users:manage",
+        }
+    }
+
+    pub fn required_role(&self) -> Role {
+        match self {
+            Action::CreateOrganisation
+            | Action::UpdateOrganisation
+            | Action::CreateCountryConnection
+            | Action::UpdateCountryConnection
+            | Action::CreateChat
+            | Action::UpdateChat => Role::Editor,
+            Action::DeleteOrganisation
+            | Action::DeleteCountryConnection
+            | Action::DeleteChat => Role::Moderator,
+            Action::ManageReferenceData
+            | Action::ManageUsers => Role::Admin,
+        }
+    }
+}
+
+pub fn authorize(actor: &AuthenticatedUser, action: Action) -> Result<(), AppError> {
+    let required_role = action.required_role();
+
+    if actor.role >= required_role {
+        return Ok(());
+    }
+
+    Err(AppError::Forbidden(json!({
+        "error": "Insufficient permissions",
+        "reason": {
+            "action": action.as_str(),
+            "requiredRole": required_role,
+            "currentRole": actor.role,
+        }
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn user(role: Role) -> AuthenticatedUser {
+        AuthenticatedUser { id: Uuid::nil(), role }
+    }
+
+    #[test]
+    fn roles_are_ordered_by_privilege() {
+        assert!(Role::Reader < Role::Editor);
+        assert!(Role::Editor < Role::Moderator);
+        assert!(Role::Moderator < Role::Admin);
+    }
+
+    #[test]
+    fn roles_round_trip_through_their_names() {
+        for role in [Role::Reader, Role::Editor, Role::Moderator, Role::Admin] {
+            assert_eq!(role.as_str().parse::<Role>().unwrap(), role);
+        }
+        assert!(matches!("owner".parse::<Role>(), Err(AppError::UnprocessableEntity(_))));
+    }
+
+    #[test]
+    fn actions_require_the_expected_role() {
+        assert_eq!(Action::CreateOrganisation.required_role(), Role::Editor);
+        assert_eq!(Action::UpdateCountryConnection.required_role(), Role::Editor);
+        assert_eq!(Action::DeleteCountryConnection.required_role(), Role::Moderator);
+        assert_eq!(Action::ManageReferenceData.required_role(), Role::Admin);
+        assert_eq!(Action::ManageUsers.required_role(), Role::Admin);
+    }
+
+    #[test]
+    fn higher_roles_inherit_lower_permissions() {
+        assert!(matches!(authorize(&user(Role::Reader), Action::CreateOrganisation), Err(AppError::Forbidden(_))));
+        assert!(authorize(&user(Role::Editor), Action::CreateOrganisation).is_ok());
+        assert!(matches!(authorize(&user(Role::Editor), Action::DeleteOrganisation), Err(AppError::Forbidden(_))));
+        assert!(authorize(&user(Role::Moderator), Action::DeleteOrganisation).is_ok());
+        assert!(matches!(authorize(&user(Role::Moderator), Action::ManageUsers), Err(AppError::Forbidden(_))));
+        assert!(authorize(&user(Role::Admin), Action::ManageUsers).is_ok());
+    }
+}
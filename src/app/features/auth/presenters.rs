@@ -0,0 +1,114 @@
+use super::entities::User;
+use super::permissions::Role;
+use crate::constants::auth;
+use crate::utils::pagination::{Page, PageLinks};
+use serde::{Deserialize, Serialize};
+use actix_web::HttpResponse;
+use chrono::NaiveDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub trait AuthPresenter: Send + Sync + 'static {
+    fn to_http_res(&self) -> HttpResponse;
+    fn to_user_json(&self, item: User) -> HttpResponse;
+    fn to_multi_user_json(&self, page: Page<User>, links: PageLinks) -> HttpResponse;
+    fn to_token_pair_json(&self, access_token: String, refresh_token: String) -> HttpResponse;
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserContent {
+    pub id: Uuid,
+    pub email: String,
+    pub role: Role,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<User> for UserContent {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            role: user.role,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MultipleUsersResponse {
+    pub items: Vec<UserContent>,
+    /// Number of registered users across all pages.
+    pub total: i64,
+    pub limit: i64,
+    /// `null` for pages requested with a cursor.
+    pub offset: Option<i64>,
+    pub has_more: bool,
+    /// Pass as `cursor` to fetch the next page; `null` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+impl From<Page<User>> for MultipleUsersResponse {
+    fn from(page: Page<User>) -> Self {
+        let page = page.map(UserContent::from);
+
+        Self {
+            items: page.items,
+            total: page.total,
+            limit: page.limit,
+            offset: page.offset,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPairContent {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub token_type: String,
+    /// Access token lifetime in seconds.
+    pub expires_in: i64,
+}
+
+#[derive(Clone)]
+pub struct AuthPresenterImpl {}
+impl AuthPresenterImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+impl AuthPresenter for AuthPresenterImpl {
+    fn to_http_res(&self) -> HttpResponse {
+        HttpResponse::Ok().json("OK")
+    }
+
+    fn to_user_json(&self, item: User) -> HttpResponse {
+        let response_content = UserContent::from(item);
+
+        HttpResponse::Ok().json(response_content)
+    }
+
+    fn to_multi_user_json(&self, page: Page<User>, links: PageLinks) -> HttpResponse {
+        let mut response = links.ok(&page);
+        let response_content = MultipleUsersResponse::from(page);
+
+        response.json(response_content)
+    }
+
+    fn to_token_pair_json(&self, access_token: String, refresh_token: String) -> HttpResponse {
+        let response_content = TokenPairContent {
+            access_token,
+            refresh_token,
+            token_type: "Bearer".to_string(),
+            expires_in: auth::ACCESS_TOKEN_TTL_MINUTES * 60,
+        };
+
+        HttpResponse::Ok().json(response_content)
+    }
+}
@@ -0,0 +1,141 @@
+use super::entities::{CreateRefreshToken, CreateUser, RefreshToken, User};
+use super::permissions::Role;
+use crate::error::AppError;
+use crate::utils::db::DbPool;
+use crate::utils::pagination::{Cursor, Page, PageRequest, CURSOR_TIMESTAMP_FORMAT};
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+pub trait AuthRepository: Send + Sync + 'static {
+    fn create_user(
+        &self,
+        params: CreateUserRepositoryInput
+    ) -> Result<User, AppError>;
+
+    fn fetch_user(
+        &self,
+        id: Uuid
+    ) -> Result<User, AppError>;
+
+    fn fetch_user_by_email(
+        &self,
+        email: &str
+    ) -> Result<Option<User>, AppError>;
+
+    fn fetch_users(
+        &self,
+        params: FetchUsersRepositoryInput
+    ) -> Result<Page<User>, AppError>;
+
+    fn update_user_role(
+        &self,
+        id: Uuid,
+        role: Role
+    ) -> Result<User, AppError>;
+
+    fn create_refresh_token(
+        &self,
+        params: CreateRefreshTokenRepositoryInput
+    ) -> Result<RefreshToken, AppError>;
+
+    fn revoke_refresh_token(
+        &self,
+        id: Uuid,
+        user_id: Uuid
+    ) -> Result<usize, AppError>;
+}
+
+#[derive(Clone)]
+pub struct AuthRepositoryImpl {
+    pool: DbPool
+}
+impl AuthRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl AuthRepository for AuthRepositoryImpl {
+    fn create_user(&self, params: CreateUserRepositoryInput) -> Result<User, AppError> {
+        let connection = &mut self.pool.get()?;
+        let new_user = User::create(
+            connection,
+            &CreateUser {
+                email: params.email,
+                password_hash: params.password_hash,
+                role: params.role,
+            }
+        )?;
+
+        Ok(new_user)
+    }
+
+    fn fetch_user(&self, id: Uuid) -> Result<User, AppError> {
+        let connection = &mut self.pool.get()?;
+        let user = User::fetch_by_id(connection, id)?;
+
+        Ok(user)
+    }
+
+    fn fetch_user_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
+        let connection = &mut self.pool.get()?;
+        let user = User::fetch_by_email(connection, email)?;
+
+        Ok(user)
+    }
+
+    fn fetch_users(&self, params: FetchUsersRepositoryInput) -> Result<Page<User>, AppError> {
+        let connection = &mut self.pool.get()?;
+        let total = User::count(connection)?;
+        let users = User::fetch_all(connection, &params.page)?;
+        let page = Page::new(users, total, &params.page, |user| {
+            Cursor::new(Some(user.created_at.format(CURSOR_TIMESTAMP_FORMAT).to_string()), user.id)
+        });
+
+        Ok(page)
+    }
+
+    fn update_user_role(&self, id: Uuid, role: Role) -> Result<User, AppError> {
+        let connection = &mut self.pool.get()?;
+        let updated_user = User::update_role(connection, id, role)?;
+
+        Ok(updated_user)
+    }
+
+    fn create_refresh_token(&self, params: CreateRefreshTokenRepositoryInput) -> Result<RefreshToken, AppError> {
+        let connection = &mut self.pool.get()?;
+        let token = RefreshToken::create(
+            connection,
+            &CreateRefreshToken {
+                id: params.id,
+                user_id: params.user_id,
+                expires_at: params.expires_at,
+            }
+        )?;
+
+        Ok(token)
+    }
+
+    fn revoke_refresh_token(&self, id: Uuid, user_id: Uuid) -> Result<usize, AppError> {
+        let connection = &mut self.pool.get()?;
+        let revoked = RefreshToken::revoke(connection, id, user_id)?;
+
+        Ok(revoked)
+    }
+}
+
+pub struct CreateUserRepositoryInput {
+    pub email: String,
+    pub password_hash: String,
+    pub role: Role,
+}
+
+pub struct FetchUsersRepositoryInput {
+    pub page: PageRequest,
+}
+
+pub struct CreateRefreshTokenRepositoryInput {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub expires_at: NaiveDateTime,
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use super::permissions::Role;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct RegisterRequest {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateUserRoleRequest {
+    pub role: Role,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema, IntoParams)]
+pub struct UsersListQueryParams {
+    /// Page size, default 20, at most 100.
+    pub limit: Option<i64>,
+    /// Rows to skip; cannot be combined with `cursor`.
+    pub offset: Option<i64>,
+    /// `nextCursor` of the previous page, to continue after it.
+    pub cursor: Option<String>,
+}
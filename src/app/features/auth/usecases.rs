@@ -0,0 +1,168 @@
+use crate::app::drivers::middlewares::auth::AuthenticatedUser;
+use crate::constants::auth;
+use crate::error::AppError;
+use crate::utils::pagination::{PageLinks, PageRequest};
+use crate::utils::jwt::{issue_token, verify_token, TokenKind};
+use super::{
+    entities::User,
+    permissions::{authorize, Action, Role},
+    presenters::AuthPresenter,
+    repositories::{AuthRepository, CreateRefreshTokenRepositoryInput, CreateUserRepositoryInput, FetchUsersRepositoryInput},
+};
+use std::sync::Arc;
+use actix_web::HttpResponse;
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct AuthUsecase {
+    auth_repo: Arc<dyn AuthRepository>,
+    auth_presenter: Arc<dyn AuthPresenter>,
+}
+
+impl AuthUsecase {
+    pub fn new(
+        auth_repo: Arc<dyn AuthRepository>,
+        auth_presenter: Arc<dyn AuthPresenter>,
+    ) -> Self {
+        Self {
+            auth_repo,
+            auth_presenter,
+        }
+    }
+
+    pub fn register(&self, params: RegisterUsecaseInput) -> Result<HttpResponse, AppError> {
+        let email = params.email.trim().to_lowercase();
+
+        if !email.contains('@') {
+            return Err(AppError::UnprocessableEntity(json!({ "error": "Invalid email address" })));
+        }
+        if params.password.chars().count() < auth::MIN_PASSWORD_LENGTH {
+            return Err(AppError::UnprocessableEntity(json!({
+                "error": format!("Password must be at least {} characters long", auth::MIN_PASSWORD_LENGTH)
+            })));
+        }
+        if self.auth_repo.fetch_user_by_email(&email)?.is_some() {
+            return Err(AppError::UnprocessableEntity(json!({ "error": "Email is already registered" })));
+        }
+
+        // Every account starts as a reader; the first admin is promoted
+        // out-of-band with the `promote_admin` script.
+        let new_user = self.auth_repo
+            .create_user(
+                CreateUserRepositoryInput {
+                    email,
+                    password_hash: bcrypt::hash(&params.password, bcrypt::DEFAULT_COST)?,
+                    role: Role::Reader,
+                }
+            )?;
+        let response = self.auth_presenter.to_user_json(new_user);
+
+        Ok(response)
+    }
+
+    pub fn login(&self, params: LoginUsecaseInput) -> Result<HttpResponse, AppError> {
+        let email = params.email.trim().to_lowercase();
+        let user = self.auth_repo
+            .fetch_user_by_email(&email)?
+            .filter(|user| bcrypt::verify(&params.password, &user.password_hash).unwrap_or(false))
+            .ok_or_else(|| AppError::Unauthorized(json!({ "error": "Invalid email or password" })))?;
+
+        self.issue_token_pair(&user)
+    }
+
+    /// Exchanges a refresh token for a new token pair. The presented refresh
+    /// token is revoked, so every refresh token can be used only once; of
+    /// concurrent requests with the same token only the one that revokes it
+    /// gets a new pair.
+    pub fn refresh(&self, params: RefreshTokenUsecaseInput) -> Result<HttpResponse, AppError> {
+        let claims = verify_token(&params.refresh_token, TokenKind::Refresh)?;
+        if self.auth_repo.revoke_refresh_token(claims.jti, claims.sub)? == 0 {
+            return Err(AppError::Unauthorized(json!({ "error": "Refresh token has been revoked" })));
+        }
+
+        let user = self.auth_repo.fetch_user(claims.sub)?;
+
+        self.issue_token_pair(&user)
+    }
+
+    pub fn logout(&self, params: RefreshTokenUsecaseInput) -> Result<HttpResponse, AppError> {
+        let claims = verify_token(&params.refresh_token, TokenKind::Refresh)?;
+        self.auth_repo.revoke_refresh_token(claims.jti, claims.sub)?;
+        let response = self.auth_presenter.to_http_res();
+
+        Ok(response)
+    }
+
+    pub fn fetch_users(
+        &self,
+        actor: &AuthenticatedUser,
+        params: FetchUsersUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageUsers)?;
+        let users = self.auth_repo
+            .fetch_users(
+                FetchUsersRepositoryInput {
+                    page: params.page,
+                }
+            )?;
+        let response = self.auth_presenter.to_multi_user_json(users, params.links);
+
+        Ok(response)
+    }
+
+    pub fn update_user_role(
+        &self,
+        actor: &AuthenticatedUser,
+        id: Uuid,
+        role: Role,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageUsers)?;
+        if actor.id == id && role < Role::Admin {
+            return Err(AppError::UnprocessableEntity(json!({ "error": "Admins cannot demote themselves" })));
+        }
+
+        let updated_user = self.auth_repo.update_user_role(id, role)?;
+        let response = self.auth_presenter.to_user_json(updated_user);
+
+        Ok(response)
+    }
+
+    fn issue_token_pair(&self, user: &User) -> Result<HttpResponse, AppError> {
+        let user_id = user.id;
+        let access_token = issue_token(user_id, user.role, TokenKind::Access)?;
+        let refresh_token = issue_token(user_id, user.role, TokenKind::Refresh)?;
+
+        self.auth_repo
+            .create_refresh_token(
+                CreateRefreshTokenRepositoryInput {
+                    id: refresh_token.claims.jti,
+                    user_id,
+                    expires_at: refresh_token.claims.expires_at(),
+                }
+            )?;
+        let response = self.auth_presenter
+            .to_token_pair_json(access_token.token, refresh_token.token);
+
+        Ok(response)
+    }
+}
+
+pub struct RegisterUsecaseInput {
+    pub email: String,
+    pub password: String,
+}
+
+pub struct LoginUsecaseInput {
+    pub email: String,
+    pub password: String,
+}
+
+pub struct RefreshTokenUsecaseInput {
+    pub refresh_token: String,
+}
+
+pub struct FetchUsersUsecaseInput {
+    pub page: PageRequest,
+    pub links: PageLinks,
+}
@@ -0,0 +1,174 @@
+use super::{
+    requests::{ChatsListQueryParams, CreateChatRequest, UpdateChatRequest},
+    usecases::{CreateChatUsecaseInput, FetchChatsUsecaseInput, UpdateChatUsecaseInput},
+};
+use crate::app::drivers::middlewares::{auth::AuthenticatedUser, state::AppState};
+use crate::error::AppError;
+use crate::utils::pagination::{PageLinks, PageRequest};
+use crate::utils::sorting::Sort;
+use actix_web::{web::{Data, Json, Path, Query}, HttpRequest, HttpResponse};
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/chat/list",
+    context_path = "/api",
+    params(ChatsListQueryParams),
+    responses(
+        (status = 200, description = "Chats list response", body = super::presenters::MultipleChatsResponse, headers(
+            ("Link" = String, description = "RFC 8288 `first`, `prev`, `next` and `last` page links")
+        )),
+        (status = 422, description = "Unknown sort field, invalid cursor or page size out of range", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Chat"
+)]
+pub async fn list(
+    state: Data<AppState>,
+    req: HttpRequest,
+    query: Query<ChatsListQueryParams>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .chat_usecase
+        .fetch_chats(
+            FetchChatsUsecaseInput {
+                origin_country_connection_id: query.origin_country_connection_id,
+                app: query.app.clone(),
+                sort: Sort::parse(query.sort.as_deref())?,
+                page: PageRequest::parse(query.limit, query.offset, query.cursor.as_deref())?,
+                links: PageLinks::from_request(&req),
+            }
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/chat/fetch/{id}",
+    context_path = "/api",
+    params(
+        ("id" = Uuid, Path, description = "Chat ID to fetch")
+    ),
+    responses(
+        (status = 200, description = "Chat fetched successfully", body = super::presenters::ChatContent),
+        (status = 400, description = "Bad request", body = AppError),
+        (status = 404, description = "Chat not found", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Chat"
+)]
+pub async fn fetch(
+    state: Data<AppState>,
+    _req: HttpRequest,
+    id: Path<Uuid>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .chat_usecase
+        .fetch_chat(id.into_inner())
+}
+
+#[utoipa::path(
+    post,
+    path = "/chat/create",
+    context_path = "/api",
+    request_body = CreateChatRequest,
+    responses(
+        (status = 200, description = "Chat created successfully", body = super::presenters::ChatContent),
+        (status = 400, description = "Bad request", body = AppError),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Chat"
+)]
+pub async fn create(
+    state: Data<AppState>,
+    _req: HttpRequest,
+    user: AuthenticatedUser,
+    form: Json<CreateChatRequest>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .chat_usecase
+        .create_chat(
+            &user,
+            CreateChatUsecaseInput {
+                app: form.app.clone(),
+                origin_country_connection_id: form.origin_country_connection_id,
+                link: form.link.clone(),
+                info: form.info.clone(),
+            }
+        )
+}
+
+#[utoipa::path(
+    put,
+    path = "/chat/update/{id}",
+    context_path = "/api",
+    request_body = UpdateChatRequest,
+    params(
+        ("id" = Uuid, Path, description = "Chat ID to update")
+    ),
+    responses(
+        (status = 200, description = "Chat updated successfully", body = super::presenters::ChatContent),
+        (status = 400, description = "Bad request", body = AppError),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 422, description = "No fields to update", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Chat"
+)]
+pub async fn update(
+    state: Data<AppState>,
+    _req: HttpRequest,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    form: Json<UpdateChatRequest>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .chat_usecase
+        .update_chat(
+            &user,
+            id.into_inner(),
+            UpdateChatUsecaseInput {
+                app: form.app.clone(),
+                origin_country_connection_id: form.origin_country_connection_id,
+                link: form.link.clone(),
+                info: form.info.clone(),
+            }
+        )
+}
+
+#[utoipa::path(
+    delete,
+    path = "/chat/delete/{id}",
+    context_path = "/api",
+    params(
+        ("id" = Uuid, Path, description = "Chat ID to delete")
+    ),
+    responses(
+        (status = 200, description = "Chat deleted successfully"),
+        (status = 400, description = "Bad request", body = AppError),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Chat"
+)]
+pub async fn delete(
+    state: Data<AppState>,
+    _req: HttpRequest,
+    user: AuthenticatedUser,
+    id: Path<Uuid>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .chat_usecase
+        .delete_chat(&user, id.into_inner())
+}
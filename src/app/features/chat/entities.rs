@@ -0,0 +1,174 @@
+use crate::app::features::country_connection::entities::CountryConnection;
+use crate::data::schema::chats;
+use crate::error::*;
+use crate::utils::pagination::{PageRequest, CURSOR_TIMESTAMP_FORMAT};
+use crate::utils::sorting::{Direction, Sort, SortField};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+#[derive(Debug, Associations, Serialize, Deserialize, Queryable, Insertable, Selectable, Clone)]
+#[diesel(belongs_to(CountryConnection, foreign_key = origin_country_connection_id))]
+#[diesel(table_name = chats)]
+pub struct Chat {
+    pub id: Uuid,
+    pub app: Option<String>,
+    pub origin_country_connection_id: Option<Uuid>,
+    pub link: Option<String>,
+    pub info: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Columns the chat list can be sorted by, besides the default order by
+/// creation time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatSortField {
+    App,
+    CreatedAt,
+}
+
+impl SortField for ChatSortField {
+    const ALLOWED: &'static [(&'static str, Self)] = &[
+        ("app", Self::App),
+        ("created_at", Self::CreatedAt),
+    ];
+}
+
+#[derive(Insertable, Clone)]
+#[diesel(table_name = chats)]
+pub struct CreateChat {
+    pub app: Option<String>,
+    pub origin_country_connection_id: Option<Uuid>,
+    pub link: Option<String>,
+    pub info: Option<String>,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = chats)]
+pub struct UpdateChat {
+    pub app: Option<String>,
+    pub origin_country_connection_id: Option<Uuid>,
+    pub link: Option<String>,
+    pub info: Option<String>,
+}
+
+impl Chat {
+    pub fn create(
+        conn: &mut PgConnection,
+        record: &CreateChat,
+    ) -> Result<Self, AppError> {
+        let result = diesel::insert_into(chats::table)
+            .values(record)
+            .returning(Chat::as_select())
+            .get_result::<Chat>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        chat_id: Uuid,
+        record: &UpdateChat,
+    ) -> Result<Self, AppError> {
+        let c = chats::table
+            .find(chat_id);
+        let result = diesel::update(c)
+            .set(record)
+            .returning(Chat::as_select())
+            .get_result::<Chat>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        chat_id: Uuid,
+    ) -> Result<(), AppError> {
+        let c = chats::table
+            .find(chat_id);
+        diesel::delete(c).execute(conn)?;
+
+        Ok(())
+    }
+
+    pub fn fetch_by_id(
+        conn: &mut PgConnection,
+        chat_id: Uuid,
+    ) -> Result<Self, AppError> {
+        let result = chats::table
+            .find(chat_id)
+            .select(Chat::as_select())
+            .get_result::<Chat>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn fetch_with_filters(
+        conn: &mut PgConnection,
+        origin_country_connection_id: Option<Uuid>,
+        app: Option<&str>,
+        sort: &Sort<ChatSortField>,
+        page: &PageRequest,
+    ) -> Result<Vec<Self>, AppError> {
+        let mut query = Self::filtered(origin_country_connection_id, app);
+        for &(field, direction) in &sort.fields {
+            query = match (field, direction) {
+                (ChatSortField::App, Direction::Asc) => query.then_order_by(chats::app.asc().nulls_last()),
+                (ChatSortField::App, Direction::Desc) => query.then_order_by(chats::app.desc().nulls_last()),
+                (ChatSortField::CreatedAt, Direction::Asc) => query.then_order_by(chats::created_at.asc()),
+                (ChatSortField::CreatedAt, Direction::Desc) => query.then_order_by(chats::created_at.desc()),
+            };
+        }
+        let mut query = query
+            .then_order_by(chats::created_at.asc())
+            .then_order_by(chats::id.asc())
+            .limit(page.fetch_limit());
+        query = match page {
+            PageRequest::Offset { offset, .. } => query.offset(*offset),
+            PageRequest::Cursor { after, .. } => {
+                let created_at = after.parse_key(|key| NaiveDateTime::parse_from_str(key, CURSOR_TIMESTAMP_FORMAT).ok())?;
+                query.filter(
+                    chats::created_at.gt(created_at)
+                        .or(chats::created_at.eq(created_at).and(chats::id.gt(after.id))),
+                )
+            }
+        };
+        let result = query
+            .select(Chat::as_select())
+            .load::<Chat>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn count_with_filters(
+        conn: &mut PgConnection,
+        origin_country_connection_id: Option<Uuid>,
+        app: Option<&str>,
+    ) -> Result<i64, AppError> {
+        let result = Self::filtered(origin_country_connection_id, app)
+            .count()
+            .get_result::<i64>(conn)?;
+
+        Ok(result)
+    }
+
+    fn filtered(
+        origin_country_connection_id: Option<Uuid>,
+        app: Option<&str>,
+    ) -> chats::BoxedQuery<'static, Pg> {
+        let mut query = chats::table.into_boxed();
+
+        if let Some(connection_id) = origin_country_connection_id {
+            query = query.filter(chats::origin_country_connection_id.eq(connection_id));
+        }
+
+        if let Some(app) = app {
+            // Case-insensitive exact match, so "telegram" finds "Telegram".
+            query = query.filter(chats::app.ilike(app.replace('%', "\\%").replace('_', "\\_")));
+        }
+
+        query
+    }
+}
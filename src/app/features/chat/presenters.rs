@@ -0,0 +1,92 @@
+use super::entities::Chat;
+use crate::utils::pagination::{Page, PageLinks};
+use serde::{Deserialize, Serialize};
+use actix_web::HttpResponse;
+use chrono::NaiveDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub trait ChatPresenter: Send + Sync + 'static {
+    fn to_http_res(&self) -> HttpResponse;
+    fn to_single_json(&self, item: Chat) -> HttpResponse;
+    fn to_multi_json(&self, page: Page<Chat>, links: PageLinks) -> HttpResponse;
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatContent {
+    pub id: Uuid,
+    pub app: Option<String>,
+    pub origin_country_connection_id: Option<Uuid>,
+    pub link: Option<String>,
+    pub info: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<Chat> for ChatContent {
+    fn from(chat: Chat) -> Self {
+        Self {
+            id: chat.id,
+            app: chat.app,
+            origin_country_connection_id: chat.origin_country_connection_id,
+            link: chat.link,
+            info: chat.info,
+            created_at: chat.created_at,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MultipleChatsResponse {
+    pub items: Vec<ChatContent>,
+    /// Number of chats matching the filters across all pages.
+    pub total: i64,
+    pub limit: i64,
+    /// `null` for pages requested with a cursor.
+    pub offset: Option<i64>,
+    pub has_more: bool,
+    /// Pass as `cursor` to fetch the next page; `null` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+impl From<Page<Chat>> for MultipleChatsResponse {
+    fn from(page: Page<Chat>) -> Self {
+        let page = page.map(ChatContent::from);
+
+        Self {
+            items: page.items,
+            total: page.total,
+            limit: page.limit,
+            offset: page.offset,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ChatPresenterImpl {}
+impl ChatPresenterImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+impl ChatPresenter for ChatPresenterImpl {
+    fn to_http_res(&self) -> HttpResponse {
+        HttpResponse::Ok().json("OK")
+    }
+
+    fn to_single_json(&self, item: Chat) -> HttpResponse {
+        let response_content = ChatContent::from(item);
+
+        HttpResponse::Ok().json(response_content)
+    }
+
+    fn to_multi_json(&self, page: Page<Chat>, links: PageLinks) -> HttpResponse {
+        let mut response = links.ok(&page);
+        let response_content = MultipleChatsResponse::from(page);
+
+        response.json(response_content)
+    }
+}
@@ -0,0 +1,133 @@
+use super::entities::{Chat, ChatSortField, CreateChat, UpdateChat};
+use crate::error::AppError;
+use crate::utils::db::DbPool;
+use crate::utils::pagination::{Cursor, Page, PageRequest, CURSOR_TIMESTAMP_FORMAT};
+use crate::utils::sorting::Sort;
+use uuid::Uuid;
+
+pub trait ChatRepository: Send + Sync + 'static {
+    fn fetch_chats(
+        &self,
+        params: FetchChatsRepositoryInput
+    ) -> Result<Page<Chat>, AppError>;
+
+    fn fetch_chat(
+        &self,
+        id: Uuid
+    ) -> Result<Chat, AppError>;
+
+    fn create_chat(
+        &self,
+        params: CreateChatRepositoryInput
+    ) -> Result<Chat, AppError>;
+
+    fn update_chat(
+        &self,
+        id: Uuid,
+        params: UpdateChatRepositoryInput
+    ) -> Result<Chat, AppError>;
+
+    fn delete_chat(
+        &self,
+        id: Uuid
+    ) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct ChatRepositoryImpl {
+    pool: DbPool
+}
+impl ChatRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl ChatRepository for ChatRepositoryImpl {
+    fn fetch_chats(&self, params: FetchChatsRepositoryInput) -> Result<Page<Chat>, AppError> {
+        let connection = &mut self.pool.get()?;
+        let total = Chat::count_with_filters(
+            connection,
+            params.origin_country_connection_id,
+            params.app.as_deref(),
+        )?;
+        let chats = Chat::fetch_with_filters(
+            connection,
+            params.origin_country_connection_id,
+            params.app.as_deref(),
+            &params.sort,
+            &params.page,
+        )?;
+        let page = Page::new(chats, total, &params.page, |chat| {
+            Cursor::new(Some(chat.created_at.format(CURSOR_TIMESTAMP_FORMAT).to_string()), chat.id)
+        });
+
+        Ok(if params.sort.is_default() { page } else { page.without_cursor() })
+    }
+
+    fn fetch_chat(&self, id: Uuid) -> Result<Chat, AppError> {
+        let connection = &mut self.pool.get()?;
+        let chat = Chat::fetch_by_id(connection, id)?;
+
+        Ok(chat)
+    }
+
+    fn create_chat(&self, params: CreateChatRepositoryInput) -> Result<Chat, AppError> {
+        let connection = &mut self.pool.get()?;
+        let new_chat = Chat::create(
+            connection,
+            &CreateChat {
+                app: params.app,
+                origin_country_connection_id: params.origin_country_connection_id,
+                link: params.link,
+                info: params.info,
+            }
+        )?;
+
+        Ok(new_chat)
+    }
+
+    fn update_chat(&self, id: Uuid, params: UpdateChatRepositoryInput) -> Result<Chat, AppError> {
+        let connection = &mut self.pool.get()?;
+        let updated_chat = Chat::update(
+            connection,
+            id,
+            &UpdateChat {
+                app: params.app,
+                origin_country_connection_id: params.origin_country_connection_id,
+                link: params.link,
+                info: params.info,
+            }
+        )?;
+
+        Ok(updated_chat)
+    }
+
+    fn delete_chat(&self, id: Uuid) -> Result<(), AppError> {
+        let connection = &mut self.pool.get()?;
+        Chat::delete(connection, id)?;
+
+        Ok(())
+    }
+}
+
+pub struct FetchChatsRepositoryInput {
+    pub origin_country_connection_id: Option<Uuid>,
+    pub app: Option<String>,
+    pub sort: Sort<ChatSortField>,
+    pub page: PageRequest,
+}
+
+pub struct CreateChatRepositoryInput {
+    pub app: Option<String>,
+    pub origin_country_connection_id: Option<Uuid>,
+    pub link: Option<String>,
+    pub info: Option<String>,
+}
+
+pub struct UpdateChatRepositoryInput {
+    pub app: Option<String>,
+    pub origin_country_connection_id: Option<Uuid>,
+    pub link: Option<String>,
+    pub info: Option<String>,
+}
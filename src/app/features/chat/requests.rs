@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateChatRequest {
+    pub app: Option<String>,
+    pub origin_country_connection_id: Option<Uuid>,
+    pub link: Option<String>,
+    pub info: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateChatRequest {
+    pub app: Option<String>,
+    pub origin_country_connection_id: Option<Uuid>,
+    pub link: Option<String>,
+    pub info: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, IntoParams, Debug)]
+pub struct ChatsListQueryParams {
+    /// Only chats belonging to this country connection.
+    pub origin_country_connection_id: Option<Uuid>,
+    /// Messenger name, matched case-insensitively (e.g. `telegram`).
+    pub app: Option<String>,
+    /// Page size, default 20, at most 100.
+    pub limit: Option<i64>,
+    /// Rows to skip; cannot be combined with `cursor`.
+    pub offset: Option<i64>,
+    /// `nextCursor` of the previous page, to continue after it. Only
+    /// available in the default order.
+    pub cursor: Option<String>,
+    /// Comma separated `app` or `created_at`, each optionally prefixed with
+    /// `-` for descending order; default `created_at`.
+    #[param(example = "app,-created_at")]
+    pub sort: Option<String>,
+}
@@ -0,0 +1,140 @@
+use crate::app::drivers::middlewares::auth::AuthenticatedUser;
+use crate::app::features::auth::permissions::{authorize, Action};
+use crate::error::AppError;
+use crate::utils::pagination::{PageLinks, PageRequest};
+use crate::utils::sorting::Sort;
+use super::{
+    entities::ChatSortField,
+    presenters::ChatPresenter,
+    repositories::{ChatRepository, CreateChatRepositoryInput, FetchChatsRepositoryInput, UpdateChatRepositoryInput},
+};
+use std::sync::Arc;
+use actix_web::HttpResponse;
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ChatUsecase {
+    chat_repo: Arc<dyn ChatRepository>,
+    chat_presenter: Arc<dyn ChatPresenter>,
+}
+
+impl ChatUsecase {
+    pub fn new(
+        chat_repo: Arc<dyn ChatRepository>,
+        chat_presenter: Arc<dyn ChatPresenter>,
+    ) -> Self {
+        Self {
+            chat_repo,
+            chat_presenter,
+        }
+    }
+
+    pub fn fetch_chat(&self, id: Uuid) -> Result<HttpResponse, AppError> {
+        let chat = self.chat_repo.fetch_chat(id)?;
+        let response = self.chat_presenter.to_single_json(chat);
+
+        Ok(response)
+    }
+
+    pub fn fetch_chats(
+        &self,
+        params: FetchChatsUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        params.sort.check_page(&params.page)?;
+        let chats = self.chat_repo
+            .fetch_chats(
+                FetchChatsRepositoryInput {
+                    origin_country_connection_id: params.origin_country_connection_id,
+                    app: params.app,
+                    sort: params.sort,
+                    page: params.page,
+                }
+            )?;
+        let response = self.chat_presenter.to_multi_json(chats, params.links);
+
+        Ok(response)
+    }
+
+    pub fn create_chat(
+        &self,
+        actor: &AuthenticatedUser,
+        params: CreateChatUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::CreateChat)?;
+        let new_chat = self.chat_repo
+            .create_chat(
+                CreateChatRepositoryInput {
+                    app: params.app,
+                    origin_country_connection_id: params.origin_country_connection_id,
+                    link: params.link,
+                    info: params.info,
+                }
+            )?;
+        let response = self.chat_presenter.to_single_json(new_chat);
+
+        Ok(response)
+    }
+
+    pub fn update_chat(
+        &self,
+        actor: &AuthenticatedUser,
+        id: Uuid,
+        params: UpdateChatUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::UpdateChat)?;
+        let has_changes = params.app.is_some()
+            || params.origin_country_connection_id.is_some()
+            || params.link.is_some()
+            || params.info.is_some();
+
+        if !has_changes {
+            return Err(AppError::UnprocessableEntity(json!({ "error": "No fields to update" })));
+        }
+
+        let updated_chat = self.chat_repo
+            .update_chat(
+                id,
+                UpdateChatRepositoryInput {
+                    app: params.app,
+                    origin_country_connection_id: params.origin_country_connection_id,
+                    link: params.link,
+                    info: params.info,
+                }
+            )?;
+        let response = self.chat_presenter.to_single_json(updated_chat);
+
+        Ok(response)
+    }
+
+    pub fn delete_chat(&self, actor: &AuthenticatedUser, id: Uuid) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::DeleteChat)?;
+        self.chat_repo
+            .delete_chat(id)?;
+        let response = self.chat_presenter.to_http_res();
+
+        Ok(response)
+    }
+}
+
+pub struct FetchChatsUsecaseInput {
+    pub origin_country_connection_id: Option<Uuid>,
+    pub app: Option<String>,
+    pub sort: Sort<ChatSortField>,
+    pub page: PageRequest,
+    pub links: PageLinks,
+}
+
+pub struct CreateChatUsecaseInput {
+    pub app: Option<String>,
+    pub origin_country_connection_id: Option<Uuid>,
+    pub link: Option<String>,
+    pub info: Option<String>,
+}
+
+pub struct UpdateChatUsecaseInput {
+    pub app: Option<String>,
+    pub origin_country_connection_id: Option<Uuid>,
+    pub link: Option<String>,
+    pub info: Option<String>,
+}
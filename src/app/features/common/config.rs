@@ -1,10 +1,40 @@
 use actix_web::{web, web::ServiceConfig};
-use super::controllers::{fetch_all_countries};
+use super::controllers::{
+    create_country, create_organisation_type, delete_country, delete_organisation_type, fetch_all_countries, fetch_countries_topology,
+    fetch_country, fetch_country_at, fetch_country_by_code, fetch_country_by_name, fetch_organisation_type, fetch_organisation_types,
+    update_country, update_organisation_type,
+};
 
 pub fn configure_services(cfg: &mut ServiceConfig) -> () {
     cfg.service(
         web::scope("/common")
             .route("/countries", web::get()
                 .to(fetch_all_countries))
+            .route("/countries/create", web::post()
+                .to(create_country))
+            .route("/countries/update/{id}", web::put()
+                .to(update_country))
+            .route("/countries/delete/{id}", web::delete()
+                .to(delete_country))
+            .route("/countries/at", web::get()
+                .to(fetch_country_at))
+            .route("/countries/topojson", web::get()
+                .to(fetch_countries_topology))
+            .route("/countries/by-code/{iso}", web::get()
+                .to(fetch_country_by_code))
+            .route("/countries/by-name/{name}", web::get()
+                .to(fetch_country_by_name))
+            .route("/countries/{id}", web::get()
+                .to(fetch_country))
+            .route("/organisation-types", web::get()
+                .to(fetch_organisation_types))
+            .route("/organisation-types/create", web::post()
+                .to(create_organisation_type))
+            .route("/organisation-types/update/{id}", web::put()
+                .to(update_organisation_type))
+            .route("/organisation-types/delete/{id}", web::delete()
+                .to(delete_organisation_type))
+            .route("/organisation-types/{id}", web::get()
+                .to(fetch_organisation_type))
     );
 }
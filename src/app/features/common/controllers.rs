@@ -1,46 +1,475 @@
-use crate::app::drivers::middlewares::state::AppState;
+use crate::app::drivers::middlewares::{auth::AuthenticatedUser, state::AppState};
 use crate::error::AppError;
-use super::usecases::FetchAllCountriesUsecaseInput;
+use super::{
+    requests::{
+        CountriesListQueryParams, CountriesTopologyQueryParams, CountryAtQueryParams, CountryQueryParams, CreateCountryRequest, CreateOrganisationTypeRequest,
+        UpdateCountryRequest, UpdateOrganisationTypeRequest,
+    },
+    usecases::{
+        CountryIncludes, CreateCountryUsecaseInput, CreateOrganisationTypeUsecaseInput, FetchAllCountriesUsecaseInput, FetchCountriesTopologyUsecaseInput, FetchCountryAtUsecaseInput,
+        FetchCountryUsecaseInput, UpdateCountryUsecaseInput, UpdateOrganisationTypeUsecaseInput,
+    },
+};
+use crate::utils::geo::GeometryDetail;
+use crate::utils::geojson::{FeatureCollection, ResponseFormat};
+use crate::utils::pagination::{PageLinks, PageRequest};
+use crate::utils::sorting::Sort;
+use crate::utils::topojson::validate_quantization;
 use actix_web::{
+    HttpRequest,
     HttpResponse,
-    web::{Data, Query},
+    web::{Data, Json, Path, Query},
 };
-use std::cmp::min;
-use serde::Deserialize;
+use uuid::Uuid;
 
 #[utoipa::path(
     get,
     path = "/common/countries",
     context_path = "/api",
     responses(
-        (status = 200, description = "Countries list response", body = Vec<super::presenters::CountryContent>),
-    ),
-    params(
-        ("limit" = Option<i64>, Query, description = "Optional limit for the number of countries to fetch, default is 20"),
-        ("offset" = Option<i64>, Query, description = "Optional offset for pagination, default is 0")
+        (status = 200, description = "Countries list response, or a GeoJSON FeatureCollection of their borders", content(
+            (super::presenters::MultipleCountriesResponse = "application/json"),
+            (FeatureCollection = "application/geo+json")
+        ), headers(
+            ("Link" = String, description = "RFC 8288 `first`, `prev`, `next` and `last` page links")
+        )),
+        (status = 422, description = "Unknown include, format, detail or sort field, invalid cursor or page size out of range", body = AppError),
     ),
+    params(CountriesListQueryParams),
     tag = "Common"
 )]
 pub async fn fetch_all_countries(
     state: Data<AppState>,
+    req: HttpRequest,
     params: Query<CountriesListQueryParams>,
 ) -> Result<HttpResponse, AppError> {
-    let offset = min(params.offset.unwrap_or(0), 150);
-    let limit = params.limit.unwrap_or(20);
-
     state
         .di_container
         .common_usecase
         .fetch_all_countries(
             FetchAllCountriesUsecaseInput {
-                limit,
-                offset,
+                sort: Sort::parse(params.sort.as_deref())?,
+                page: PageRequest::parse(params.limit, params.offset, params.cursor.as_deref())?,
+                includes: CountryIncludes::parse(params.include.as_deref())?,
+                detail: GeometryDetail::parse(params.detail.as_deref())?,
+                format: ResponseFormat::negotiate(params.format.as_deref(), &req)?,
+                links: PageLinks::from_request(&req),
+            }
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/common/countries/topojson",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "TopoJSON Topology with a `countries` GeometryCollection whose borders are shared arcs", body = Object),
+        (status = 422, description = "Quantization out of range or unknown detail", body = AppError),
+    ),
+    params(CountriesTopologyQueryParams),
+    tag = "Common"
+)]
+pub async fn fetch_countries_topology(
+    state: Data<AppState>,
+    params: Query<CountriesTopologyQueryParams>,
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .common_usecase
+        .fetch_countries_topology(
+            FetchCountriesTopologyUsecaseInput {
+                quantization: validate_quantization(params.quantization)?,
+                detail: GeometryDetail::parse_or(params.detail.as_deref(), GeometryDetail::Medium)?,
+            }
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/common/countries/{id}",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "Country response", body = super::presenters::CountryContent),
+        (status = 404, description = "Country not found", body = AppError),
+        (status = 422, description = "Unknown include or detail", body = AppError),
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Country ID"),
+        CountryQueryParams
+    ),
+    tag = "Common"
+)]
+pub async fn fetch_country(
+    state: Data<AppState>,
+    id: Path<Uuid>,
+    params: Query<CountryQueryParams>,
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .common_usecase
+        .fetch_country(
+            FetchCountryUsecaseInput {
+                id: Some(id.into_inner()),
+                name: None,
+                iso_code: None,
+                includes: CountryIncludes::parse(params.include.as_deref())?,
+                detail: GeometryDetail::parse(params.detail.as_deref())?,
+            }
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/common/countries/by-code/{iso}",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "Country response", body = super::presenters::CountryContent),
+        (status = 404, description = "Country not found", body = AppError),
+        (status = 422, description = "Malformed ISO code, unknown include or detail", body = AppError),
+    ),
+    params(
+        ("iso" = String, Path, description = "ISO 3166-1 alpha-2 or alpha-3 code, case-insensitive, e.g. `DE` or `deu`"),
+        CountryQueryParams
+    ),
+    tag = "Common"
+)]
+pub async fn fetch_country_by_code(
+    state: Data<AppState>,
+    iso: Path<String>,
+    params: Query<CountryQueryParams>,
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .common_usecase
+        .fetch_country(
+            FetchCountryUsecaseInput {
+                id: None,
+                name: None,
+                iso_code: Some(iso.into_inner()),
+                includes: CountryIncludes::parse(params.include.as_deref())?,
+                detail: GeometryDetail::parse(params.detail.as_deref())?,
+            }
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/common/countries/by-name/{name}",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "Country response", body = super::presenters::CountryContent),
+        (status = 404, description = "Country not found", body = AppError),
+        (status = 422, description = "Unknown include or detail", body = AppError),
+    ),
+    params(
+        ("name" = String, Path, description = "Country name, matched case-insensitively, e.g. `germany`"),
+        CountryQueryParams
+    ),
+    tag = "Common"
+)]
+pub async fn fetch_country_by_name(
+    state: Data<AppState>,
+    name: Path<String>,
+    params: Query<CountryQueryParams>,
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .common_usecase
+        .fetch_country(
+            FetchCountryUsecaseInput {
+                id: None,
+                name: Some(name.into_inner()),
+                iso_code: None,
+                includes: CountryIncludes::parse(params.include.as_deref())?,
+                detail: GeometryDetail::parse(params.detail.as_deref())?,
+            }
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/common/countries/at",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "Country whose borders contain the point", body = super::presenters::CountryContent),
+        (status = 404, description = "The point is not inside any country, e.g. at sea", body = AppError),
+        (status = 422, description = "Coordinates out of range, unknown include or detail", body = AppError),
+    ),
+    params(CountryAtQueryParams),
+    tag = "Common"
+)]
+pub async fn fetch_country_at(
+    state: Data<AppState>,
+    params: Query<CountryAtQueryParams>,
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .common_usecase
+        .fetch_country_at(
+            FetchCountryAtUsecaseInput {
+                latitude: params.lat,
+                longitude: params.lon,
+                includes: CountryIncludes::parse(params.include.as_deref())?,
+                detail: GeometryDetail::parse(params.detail.as_deref())?,
+            }
+        )
+}
+
+#[utoipa::path(
+    post,
+    path = "/common/countries/create",
+    context_path = "/api",
+    request_body = CreateCountryRequest,
+    responses(
+        (status = 200, description = "Country created successfully", body = super::presenters::CountryContent),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 422, description = "Invalid country data or ISO code already taken", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Common"
+)]
+pub async fn create_country(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    form: Json<CreateCountryRequest>
+) -> Result<HttpResponse, AppError> {
+    let form = form.into_inner();
+
+    state
+        .di_container
+        .common_usecase
+        .create_country(
+            &user,
+            CreateCountryUsecaseInput {
+                name: form.name,
+                geo_json: form.geo_json,
+                flag: form.flag,
+                capital_city: form.capital_city,
+                description: form.description,
+                iso_alpha2: form.iso_alpha2,
+                iso_alpha3: form.iso_alpha3,
+            }
+        )
+}
+
+#[utoipa::path(
+    put,
+    path = "/common/countries/update/{id}",
+    context_path = "/api",
+    request_body = UpdateCountryRequest,
+    params(
+        ("id" = Uuid, Path, description = "Country ID to update")
+    ),
+    responses(
+        (status = 200, description = "Country updated successfully", body = super::presenters::CountryContent),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 404, description = "Country not found", body = AppError),
+        (status = 422, description = "Invalid country data or ISO code already taken", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Common"
+)]
+pub async fn update_country(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    form: Json<UpdateCountryRequest>
+) -> Result<HttpResponse, AppError> {
+    let form = form.into_inner();
+
+    state
+        .di_container
+        .common_usecase
+        .update_country(
+            &user,
+            id.into_inner(),
+            UpdateCountryUsecaseInput {
+                name: form.name,
+                geo_json: form.geo_json,
+                flag: form.flag,
+                capital_city: form.capital_city,
+                description: form.description,
+                iso_alpha2: form.iso_alpha2,
+                iso_alpha3: form.iso_alpha3,
+            }
+        )
+}
+
+#[utoipa::path(
+    delete,
+    path = "/common/countries/delete/{id}",
+    context_path = "/api",
+    params(
+        ("id" = Uuid, Path, description = "Country ID to delete")
+    ),
+    responses(
+        (status = 200, description = "Country and its language relations deleted successfully"),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 404, description = "Country not found", body = AppError),
+        (status = 409, description = "Country is still referenced by organisations or country connections", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Common"
+)]
+pub async fn delete_country(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .common_usecase
+        .delete_country(&user, id.into_inner())
+}
+
+#[utoipa::path(
+    get,
+    path = "/common/organisation-types",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "Organisation types list response", body = Vec<super::presenters::OrganisationTypeContent>),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Common"
+)]
+pub async fn fetch_organisation_types(
+    state: Data<AppState>,
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .common_usecase
+        .fetch_organisation_types()
+}
+
+#[utoipa::path(
+    get,
+    path = "/common/organisation-types/{id}",
+    context_path = "/api",
+    params(
+        ("id" = Uuid, Path, description = "Organisation type ID")
+    ),
+    responses(
+        (status = 200, description = "Organisation type response", body = super::presenters::OrganisationTypeContent),
+        (status = 404, description = "Organisation type not found", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Common"
+)]
+pub async fn fetch_organisation_type(
+    state: Data<AppState>,
+    id: Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .common_usecase
+        .fetch_organisation_type(id.into_inner())
+}
+
+#[utoipa::path(
+    post,
+    path = "/common/organisation-types/create",
+    context_path = "/api",
+    request_body = CreateOrganisationTypeRequest,
+    responses(
+        (status = 200, description = "Organisation type created successfully", body = super::presenters::OrganisationTypeContent),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 422, description = "Empty type or invalid hex colour", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Common"
+)]
+pub async fn create_organisation_type(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    form: Json<CreateOrganisationTypeRequest>
+) -> Result<HttpResponse, AppError> {
+    let form = form.into_inner();
+
+    state
+        .di_container
+        .common_usecase
+        .create_organisation_type(
+            &user,
+            CreateOrganisationTypeUsecaseInput {
+                org_type: form.r#type,
+                color: form.color,
+            }
+        )
+}
+
+#[utoipa::path(
+    put,
+    path = "/common/organisation-types/update/{id}",
+    context_path = "/api",
+    request_body = UpdateOrganisationTypeRequest,
+    params(
+        ("id" = Uuid, Path, description = "Organisation type ID to update")
+    ),
+    responses(
+        (status = 200, description = "Organisation type updated successfully", body = super::presenters::OrganisationTypeContent),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 404, description = "Organisation type not found", body = AppError),
+        (status = 422, description = "Empty type or invalid hex colour", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Common"
+)]
+pub async fn update_organisation_type(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    form: Json<UpdateOrganisationTypeRequest>
+) -> Result<HttpResponse, AppError> {
+    let form = form.into_inner();
+
+    state
+        .di_container
+        .common_usecase
+        .update_organisation_type(
+            &user,
+            id.into_inner(),
+            UpdateOrganisationTypeUsecaseInput {
+                org_type: form.r#type,
+                color: form.color,
             }
         )
 }
 
-#[derive(Deserialize)]
-pub struct CountriesListQueryParams {
-    limit: Option<i64>,
-    offset: Option<i64>,
+#[utoipa::path(
+    delete,
+    path = "/common/organisation-types/delete/{id}",
+    context_path = "/api",
+    params(
+        ("id" = Uuid, Path, description = "Organisation type ID to delete")
+    ),
+    responses(
+        (status = 200, description = "Organisation type deleted successfully"),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 404, description = "Organisation type not found", body = AppError),
+        (status = 409, description = "Organisation type is still used by organisations", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Common"
+)]
+pub async fn delete_organisation_type(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .common_usecase
+        .delete_organisation_type(&user, id.into_inner())
 }
@@ -1,5 +1,6 @@
 pub mod repositories;
 pub mod presenters;
+pub mod requests;
 pub mod usecases;
 pub mod controllers;
 pub mod config;
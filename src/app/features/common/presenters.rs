@@ -1,14 +1,23 @@
 use actix_web::HttpResponse;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use utoipa::ToSchema;
 use uuid::Uuid;
-use crate::data::models::{Country, OrganisationType};
+use std::collections::HashMap;
+use crate::app::features::language::presenters::LanguageContent;
+use crate::data::models::{Country, Language, OrganisationType};
+use crate::utils::geojson::{Feature, FeatureCollection};
+use crate::utils::pagination::{Page, PageLinks};
+use crate::constants::topology::CACHE_MAX_AGE_SECONDS;
+use actix_web::http::header::CACHE_CONTROL;
 
 pub trait CommonPresenter: Send + Sync + 'static {
     fn to_http_res(&self) -> HttpResponse;
-    fn to_single_country_json(&self, item: Country) -> HttpResponse;
-    fn to_multi_country_json(&self, item: Vec<Country>) -> HttpResponse;
+    fn to_single_country_json(&self, item: Country, relations: CountryRelations) -> HttpResponse;
+    fn to_multi_country_json(&self, page: Page<Country>, relations: CountryRelations, links: PageLinks) -> HttpResponse;
+    fn to_multi_country_geo_json(&self, page: Page<Country>, relations: CountryRelations, links: PageLinks) -> HttpResponse;
+    fn to_country_topo_json(&self, topology: Value) -> HttpResponse;
+    fn to_single_organization_type_json(&self, item: OrganisationType) -> HttpResponse;
     fn to_multi_organization_type_json(&self, item: Vec<OrganisationType>) -> HttpResponse;
 }
 
@@ -24,17 +33,54 @@ impl CommonPresenter for CommonPresenterImpl {
         HttpResponse::Ok().json("OK")
     }
 
-    fn to_single_country_json(&self, item: Country) -> HttpResponse {
-        let response_content = CountryContent::from(item);
+    fn to_single_country_json(&self, item: Country, mut relations: CountryRelations) -> HttpResponse {
+        let response_content = relations.attach(CountryContent::from(item));
 
         HttpResponse::Ok().json(response_content)
     }
 
-    fn to_multi_country_json(&self, item: Vec<Country>) -> HttpResponse {
-        let response_content: Vec<CountryContent> = item.iter()
-            .map(|country| CountryContent::from(country.to_owned()))
+    fn to_multi_country_json(&self, page: Page<Country>, mut relations: CountryRelations, links: PageLinks) -> HttpResponse {
+        let mut response = links.ok(&page);
+        let page = page.map(|country| relations.attach(CountryContent::from(country)));
+        let response_content = MultipleCountriesResponse {
+            items: page.items,
+            total: page.total,
+            limit: page.limit,
+            offset: page.offset,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        };
+
+        response.json(response_content)
+    }
+
+    fn to_multi_country_geo_json(&self, page: Page<Country>, mut relations: CountryRelations, links: PageLinks) -> HttpResponse {
+        let response = links.ok(&page);
+        let features = page.items.into_iter()
+            .map(|country| {
+                let mut content = relations.attach(CountryContent::from(country));
+                let geometry = content.geo_json.take();
+                let mut properties = json!(content);
+                if let Value::Object(fields) = &mut properties {
+                    fields.remove("geoJson");
+                }
+
+                Feature::new(content.id, geometry, properties)
+            })
             .collect();
 
+        FeatureCollection::new(features).respond(response)
+    }
+
+    fn to_country_topo_json(&self, topology: Value) -> HttpResponse {
+        HttpResponse::Ok()
+            .insert_header((CACHE_CONTROL, format!("public, max-age={}", CACHE_MAX_AGE_SECONDS)))
+            .json(topology)
+    }
+
+    fn to_single_organization_type_json(&self, item: OrganisationType) -> HttpResponse {
+        let response_content = OrganisationTypeContent::from(item);
+
         HttpResponse::Ok().json(response_content)
     }
 
@@ -47,6 +93,20 @@ impl CommonPresenter for CommonPresenterImpl {
     }
 }
 
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MultipleCountriesResponse {
+    pub items: Vec<CountryContent>,
+    /// Number of countries across all pages.
+    pub total: i64,
+    pub limit: i64,
+    /// `null` for pages requested with a cursor.
+    pub offset: Option<i64>,
+    pub has_more: bool,
+    /// Pass as `cursor` to fetch the next page; `null` on the last page.
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CountryContent {
@@ -56,17 +116,113 @@ pub struct CountryContent {
     pub flag: Option<String>,
     pub capital_city: Option<String>,
     pub description: Option<String>,
+    /// ISO 3166-1 alpha-2 code, e.g. `DE`.
+    pub iso_alpha2: Option<String>,
+    /// ISO 3166-1 alpha-3 code, e.g. `DEU`.
+    pub iso_alpha3: Option<String>,
+    /// Label point of the shape, inside the country.
+    pub centroid: Option<CentroidContent>,
+    /// `[minLon, minLat, maxLon, maxLat]`; `minLon` is greater than `maxLon`
+    /// for countries crossing the antimeridian.
+    #[schema(example = json!([5.87, 47.27, 15.04, 55.06]))]
+    pub bbox: Option<[f64; 4]>,
+    pub area_km2: Option<f64>,
+    /// Present only when requested with `include=languages`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub languages: Option<Vec<LanguageContent>>,
+    /// Countries sharing a border, present only when requested with `include=neighbours`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub neighbours: Option<Vec<CountrySummaryContent>>,
 }
 impl From<Country> for CountryContent {
     fn from(val: Country) -> Self {
+        let centroid = val.centroid_latitude
+            .zip(val.centroid_longitude)
+            .map(|(latitude, longitude)| CentroidContent { latitude, longitude });
+        let bbox = match (val.bbox_min_longitude, val.bbox_min_latitude, val.bbox_max_longitude, val.bbox_max_latitude) {
+            (Some(min_lon), Some(min_lat), Some(max_lon), Some(max_lat)) => Some([min_lon, min_lat, max_lon, max_lat]),
+            _ => None,
+        };
+
         Self {
             id: val.id,
             name: val.name.to_owned(),
             geo_json: val.geo_json,
             flag: val.flag,
             capital_city: val.capital_city,
-            description: val.description
+            description: val.description,
+            iso_alpha2: val.iso_alpha2,
+            iso_alpha3: val.iso_alpha3,
+            centroid,
+            bbox,
+            area_km2: val.area_km2,
+            languages: None,
+            neighbours: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CentroidContent {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Short form of a country used when it is embedded into another one.
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CountrySummaryContent {
+    pub id: Uuid,
+    pub name: String,
+    pub flag: Option<String>,
+    pub iso_alpha2: Option<String>,
+    pub iso_alpha3: Option<String>,
+}
+impl From<Country> for CountrySummaryContent {
+    fn from(val: Country) -> Self {
+        Self {
+            id: val.id,
+            name: val.name,
+            flag: val.flag,
+            iso_alpha2: val.iso_alpha2,
+            iso_alpha3: val.iso_alpha3,
+        }
+    }
+}
+
+/// Related records loaded on request and embedded into `CountryContent`.
+/// A `None` relation is left out of the response entirely.
+#[derive(Default)]
+pub struct CountryRelations {
+    pub languages: Option<HashMap<Uuid, Vec<Language>>>,
+    pub neighbours: Option<HashMap<Uuid, Vec<Country>>>,
+}
+impl CountryRelations {
+    fn attach(&mut self, mut content: CountryContent) -> CountryContent {
+        if let Some(languages) = self.languages.as_mut() {
+            content.languages = Some(
+                languages
+                    .remove(&content.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(LanguageContent::from)
+                    .collect()
+            );
         }
+
+        if let Some(neighbours) = self.neighbours.as_mut() {
+            content.neighbours = Some(
+                neighbours
+                    .remove(&content.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(CountrySummaryContent::from)
+                    .collect()
+            );
+        }
+
+        content
     }
 }
 
@@ -1,7 +1,12 @@
-use crate::data::models::{Country, OrganisationType};
+use crate::data::models::{Country, CountryNeighbour, CreateCountry, CreateOrganisationType, Language, OrganisationType, UpdateCountry, UpdateOrganisationType};
 use crate::error::AppError;
 use crate::utils::db::DbPool;
+use crate::utils::geo::{BoundingBox, GeometryDetail};
+use crate::utils::pagination::{Cursor, Page, PageRequest};
+use crate::utils::sorting::{Direction, Sort, SortField};
+use diesel::Connection;
 use serde_json::json;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 pub trait CommonRepository: Send + Sync + 'static {
@@ -13,7 +18,51 @@ pub trait CommonRepository: Send + Sync + 'static {
     fn get_all_countries(
         &self,
         params: GetAllCountriesRepositoryInput
-    ) -> Result<Vec<Country>, AppError>;
+    ) -> Result<Page<Country>, AppError>;
+
+    /// Full-detail geometries of the countries whose bounding box overlaps
+    /// `bounding_box`.
+    fn get_country_geometries(
+        &self,
+        bounding_box: &BoundingBox
+    ) -> Result<Vec<(Uuid, serde_json::Value)>, AppError>;
+
+    fn get_country_shapes(
+        &self,
+        detail: GeometryDetail
+    ) -> Result<Vec<(Country, serde_json::Value)>, AppError>;
+
+    fn get_country_geometries_by_ids(
+        &self,
+        country_ids: &[Uuid],
+        detail: GeometryDetail
+    ) -> Result<HashMap<Uuid, serde_json::Value>, AppError>;
+
+    fn create_country(
+        &self,
+        params: CreateCountryRepositoryInput
+    ) -> Result<Country, AppError>;
+
+    fn update_country(
+        &self,
+        id: Uuid,
+        params: UpdateCountryRepositoryInput
+    ) -> Result<Country, AppError>;
+
+    fn delete_country(
+        &self,
+        id: Uuid
+    ) -> Result<(), AppError>;
+
+    fn get_country_languages(
+        &self,
+        country_ids: &[Uuid]
+    ) -> Result<HashMap<Uuid, Vec<Language>>, AppError>;
+
+    fn get_country_neighbours(
+        &self,
+        country_ids: &[Uuid]
+    ) -> Result<HashMap<Uuid, Vec<Country>>, AppError>;
 
     fn get_organisation_type(
         &self,
@@ -23,6 +72,22 @@ pub trait CommonRepository: Send + Sync + 'static {
     fn get_all_organisation_types(
         &self
     ) -> Result<Vec<OrganisationType>, AppError>;
+
+    fn create_organisation_type(
+        &self,
+        params: CreateOrganisationTypeRepositoryInput
+    ) -> Result<OrganisationType, AppError>;
+
+    fn update_organisation_type(
+        &self,
+        id: Uuid,
+        params: UpdateOrganisationTypeRepositoryInput
+    ) -> Result<OrganisationType, AppError>;
+
+    fn delete_organisation_type(
+        &self,
+        id: Uuid
+    ) -> Result<(), AppError>;
 }
 
 #[derive(Clone)]
@@ -53,7 +118,17 @@ impl CommonRepository for CommonRepositoryImpl {
                             &name.as_str()
                         )
                     },
-                    None => Err(AppError::NotFound(json!({ "error": "Empty request params" })))
+                    None => {
+                        match params.iso_code {
+                            Some(iso_code) => {
+                                Country::get_by_iso_code(
+                                    connection,
+                                    iso_code.as_str()
+                                )
+                            },
+                            None => Err(AppError::NotFound(json!({ "error": "Empty request params" })))
+                        }
+                    }
                 }
             }
         };
@@ -61,18 +136,174 @@ impl CommonRepository for CommonRepositoryImpl {
         country_result
     }
 
-    fn get_all_countries(&self, params: GetAllCountriesRepositoryInput) -> Result<Vec<Country>, AppError> {
+    fn get_all_countries(&self, params: GetAllCountriesRepositoryInput) -> Result<Page<Country>, AppError> {
+        use crate::data::schema::countries;
+        use diesel::prelude::*;
+
+        let connection = &mut self.pool.get()?;
+        let total = countries::table
+            .count()
+            .get_result::<i64>(connection)?;
+        let mut query = countries::table
+            .select(Country::without_geometry())
+            .into_boxed();
+        // Nullable columns keep countries without a value at the end in
+        // both directions.
+        for &(field, direction) in &params.sort.fields {
+            query = match (field, direction) {
+                (CountrySortField::Name, Direction::Asc) => query.then_order_by(countries::name.asc()),
+                (CountrySortField::Name, Direction::Desc) => query.then_order_by(countries::name.desc()),
+                (CountrySortField::IsoAlpha2, Direction::Asc) => query.then_order_by(countries::iso_alpha2.asc().nulls_last()),
+                (CountrySortField::IsoAlpha2, Direction::Desc) => query.then_order_by(countries::iso_alpha2.desc().nulls_last()),
+                (CountrySortField::IsoAlpha3, Direction::Asc) => query.then_order_by(countries::iso_alpha3.asc().nulls_last()),
+                (CountrySortField::IsoAlpha3, Direction::Desc) => query.then_order_by(countries::iso_alpha3.desc().nulls_last()),
+                (CountrySortField::AreaKm2, Direction::Asc) => query.then_order_by(countries::area_km2.asc().nulls_last()),
+                (CountrySortField::AreaKm2, Direction::Desc) => query.then_order_by(countries::area_km2.desc().nulls_last()),
+            };
+        }
+        query = query
+            .then_order_by(countries::name.asc())
+            .then_order_by(countries::id.asc())
+            .limit(params.page.fetch_limit());
+        query = match &params.page {
+            PageRequest::Offset { offset, .. } => query.offset(*offset),
+            PageRequest::Cursor { after, .. } => {
+                let name = after.parse_key(|key| Some(key.to_string()))?;
+                query.filter(
+                    countries::name.gt(name.clone())
+                        .or(countries::name.eq(name).and(countries::id.gt(after.id))),
+                )
+            }
+        };
+        let countries_list = query.load::<Country>(connection)?;
+
+        let page = Page::new(countries_list, total, &params.page, |country| {
+            Cursor::new(Some(country.name.clone()), country.id)
+        });
+
+        Ok(if params.sort.is_default() { page } else { page.without_cursor() })
+    }
+
+    fn get_country_geometries(&self, bounding_box: &BoundingBox) -> Result<Vec<(Uuid, serde_json::Value)>, AppError> {
+        let connection = &mut self.pool.get()?;
+        let ids: Vec<_> = Country::get_within_bounding_box(connection, bounding_box)?
+            .iter()
+            .map(|country| country.id)
+            .collect();
+        let geometries = Country::get_geometries_by_ids(connection, &ids, GeometryDetail::Full)?;
+
+        Ok(geometries)
+    }
+
+    fn get_country_shapes(&self, detail: GeometryDetail) -> Result<Vec<(Country, serde_json::Value)>, AppError> {
         use crate::data::schema::countries;
         use diesel::prelude::*;
 
         let connection = &mut self.pool.get()?;
         let countries_list = countries::table
-            .select(countries::all_columns)
-            .limit(params.limit)
-            .offset(params.offset)
+            .select(Country::without_geometry())
+            .order(countries::name.asc())
             .load::<Country>(connection)?;
+        let ids: Vec<_> = countries_list.iter().map(|country| country.id).collect();
+        let mut geometries: HashMap<_, _> = Country::get_geometries_by_ids(connection, &ids, detail)?
+            .into_iter()
+            .collect();
+
+        let shapes = countries_list
+            .into_iter()
+            .filter_map(|country| geometries.remove(&country.id).map(|geometry| (country, geometry)))
+            .collect();
+
+        Ok(shapes)
+    }
 
-        Ok(countries_list)
+    fn get_country_geometries_by_ids(&self, country_ids: &[Uuid], detail: GeometryDetail) -> Result<HashMap<Uuid, serde_json::Value>, AppError> {
+        let connection = &mut self.pool.get()?;
+        let geometries = Country::get_geometries_by_ids(connection, country_ids, detail)?
+            .into_iter()
+            .collect();
+
+        Ok(geometries)
+    }
+
+    fn create_country(&self, params: CreateCountryRepositoryInput) -> Result<Country, AppError> {
+        let connection = &mut self.pool.get()?;
+        let new_country = connection.transaction::<_, AppError, _>(|conn| {
+            let has_geometry = params.geo_json.is_some();
+            let country = Country::create(
+                conn,
+                &CreateCountry {
+                    name: params.name,
+                    geo_json: params.geo_json,
+                    flag: params.flag,
+                    capital_city: params.capital_city,
+                    description: params.description,
+                    iso_alpha2: params.iso_alpha2,
+                    iso_alpha3: params.iso_alpha3,
+                }
+            )?;
+            if has_geometry {
+                CountryNeighbour::refresh_for(conn, country.id)?;
+            }
+
+            Ok(country)
+        })?;
+
+        Ok(new_country)
+    }
+
+    fn update_country(&self, id: Uuid, params: UpdateCountryRepositoryInput) -> Result<Country, AppError> {
+        let connection = &mut self.pool.get()?;
+        let updated_country = connection.transaction::<_, AppError, _>(|conn| {
+            let geometry_changed = params.geo_json.is_some();
+            let country = Country::update(
+                conn,
+                id,
+                &UpdateCountry {
+                    name: params.name,
+                    geo_json: params.geo_json,
+                    flag: params.flag,
+                    capital_city: params.capital_city,
+                    description: params.description,
+                    iso_alpha2: params.iso_alpha2,
+                    iso_alpha3: params.iso_alpha3,
+                }
+            )?;
+            if geometry_changed {
+                CountryNeighbour::refresh_for(conn, country.id)?;
+            }
+
+            Ok(country)
+        })?;
+
+        Ok(updated_country)
+    }
+
+    fn delete_country(&self, id: Uuid) -> Result<(), AppError> {
+        let connection = &mut self.pool.get()?;
+        Country::delete(connection, id)?;
+
+        Ok(())
+    }
+
+    fn get_country_languages(&self, country_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Language>>, AppError> {
+        let connection = &mut self.pool.get()?;
+        let mut languages_by_country: HashMap<Uuid, Vec<Language>> = HashMap::new();
+        for (country_id, language) in Language::get_by_country_ids(connection, country_ids)? {
+            languages_by_country.entry(country_id).or_default().push(language);
+        }
+
+        Ok(languages_by_country)
+    }
+
+    fn get_country_neighbours(&self, country_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Country>>, AppError> {
+        let connection = &mut self.pool.get()?;
+        let mut neighbours_by_country: HashMap<Uuid, Vec<Country>> = HashMap::new();
+        for (country_id, neighbour) in CountryNeighbour::get_by_country_ids(connection, country_ids)? {
+            neighbours_by_country.entry(country_id).or_default().push(neighbour);
+        }
+
+        Ok(neighbours_by_country)
     }
 
     fn get_organisation_type(&self, id: &Uuid) -> Result<OrganisationType, AppError> {
@@ -88,14 +319,96 @@ impl CommonRepository for CommonRepositoryImpl {
 
         Ok(all_org_types)
     }
+
+    fn create_organisation_type(&self, params: CreateOrganisationTypeRepositoryInput) -> Result<OrganisationType, AppError> {
+        let connection = &mut self.pool.get()?;
+        let new_org_type = OrganisationType::create(
+            connection,
+            &CreateOrganisationType {
+                org_type: params.org_type,
+                color: params.color,
+            }
+        )?;
+
+        Ok(new_org_type)
+    }
+
+    fn update_organisation_type(&self, id: Uuid, params: UpdateOrganisationTypeRepositoryInput) -> Result<OrganisationType, AppError> {
+        let connection = &mut self.pool.get()?;
+        let updated_org_type = OrganisationType::update(
+            connection,
+            id,
+            &UpdateOrganisationType {
+                org_type: params.org_type,
+                color: params.color,
+            }
+        )?;
+
+        Ok(updated_org_type)
+    }
+
+    fn delete_organisation_type(&self, id: Uuid) -> Result<(), AppError> {
+        let connection = &mut self.pool.get()?;
+        OrganisationType::delete(connection, id)?;
+
+        Ok(())
+    }
 }
 
 pub struct GetCountryRepositoryInput {
     pub id: Option<Uuid>,
     pub name: Option<String>,
+    pub iso_code: Option<String>,
 }
 
 pub struct GetAllCountriesRepositoryInput {
-    pub limit: i64,
-    pub offset: i64,
+    pub sort: Sort<CountrySortField>,
+    pub page: PageRequest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountrySortField {
+    Name,
+    IsoAlpha2,
+    IsoAlpha3,
+    AreaKm2,
+}
+
+impl SortField for CountrySortField {
+    const ALLOWED: &'static [(&'static str, Self)] = &[
+        ("name", Self::Name),
+        ("iso_alpha2", Self::IsoAlpha2),
+        ("iso_alpha3", Self::IsoAlpha3),
+        ("area_km2", Self::AreaKm2),
+    ];
+}
+
+pub struct CreateCountryRepositoryInput {
+    pub name: String,
+    pub geo_json: Option<serde_json::Value>,
+    pub flag: Option<String>,
+    pub capital_city: Option<String>,
+    pub description: Option<String>,
+    pub iso_alpha2: Option<String>,
+    pub iso_alpha3: Option<String>,
+}
+
+pub struct UpdateCountryRepositoryInput {
+    pub name: Option<String>,
+    pub geo_json: Option<serde_json::Value>,
+    pub flag: Option<String>,
+    pub capital_city: Option<String>,
+    pub description: Option<String>,
+    pub iso_alpha2: Option<String>,
+    pub iso_alpha3: Option<String>,
+}
+
+pub struct CreateOrganisationTypeRepositoryInput {
+    pub org_type: String,
+    pub color: Option<String>,
+}
+
+pub struct UpdateOrganisationTypeRepositoryInput {
+    pub org_type: Option<String>,
+    pub color: Option<String>,
 }
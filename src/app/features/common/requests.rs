@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateCountryRequest {
+    pub name: String,
+    /// GeoJSON `Polygon` or `MultiPolygon` geometry of the country borders.
+    pub geo_json: Option<serde_json::Value>,
+    pub flag: Option<String>,
+    pub capital_city: Option<String>,
+    pub description: Option<String>,
+    pub iso_alpha2: Option<String>,
+    pub iso_alpha3: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateCountryRequest {
+    pub name: Option<String>,
+    /// GeoJSON `Polygon` or `MultiPolygon` geometry of the country borders.
+    pub geo_json: Option<serde_json::Value>,
+    pub flag: Option<String>,
+    pub capital_city: Option<String>,
+    pub description: Option<String>,
+    pub iso_alpha2: Option<String>,
+    pub iso_alpha3: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateOrganisationTypeRequest {
+    pub r#type: String,
+    /// Hex colour, `#rgb` or `#rrggbb`.
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateOrganisationTypeRequest {
+    pub r#type: Option<String>,
+    /// Hex colour, `#rgb` or `#rrggbb`.
+    pub color: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema, IntoParams)]
+pub struct CountriesListQueryParams {
+    /// Page size, default 20, at most 100.
+    pub limit: Option<i64>,
+    /// Rows to skip; cannot be combined with `cursor`.
+    pub offset: Option<i64>,
+    /// `nextCursor` of the previous page, to continue after it. Only
+    /// available in the default order.
+    pub cursor: Option<String>,
+    /// Comma separated `name`, `iso_alpha2`, `iso_alpha3` or `area_km2`,
+    /// each optionally prefixed with `-` for descending order; default `name`.
+    #[param(example = "-area_km2,name")]
+    pub sort: Option<String>,
+    /// Comma separated relations to embed, supported: `languages`, `neighbours`.
+    pub include: Option<String>,
+    /// `json` (default) or `geojson`; `Accept: application/geo+json` also selects GeoJSON.
+    pub format: Option<String>,
+    /// Geometry detail: `none`, `low`, `medium` or `full` (default).
+    pub detail: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema, IntoParams)]
+pub struct CountriesTopologyQueryParams {
+    /// Grid steps per axis coordinates are snapped to, from 2 to 1e6, default 10000.
+    pub quantization: Option<u32>,
+    /// Geometry detail: `low`, `medium` (default) or `full`.
+    pub detail: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema, IntoParams)]
+pub struct CountryQueryParams {
+    /// Comma separated relations to embed, supported: `languages`, `neighbours`.
+    pub include: Option<String>,
+    /// Geometry detail: `none`, `low`, `medium` or `full` (default).
+    pub detail: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema, IntoParams)]
+pub struct CountryAtQueryParams {
+    /// Latitude in decimal degrees.
+    pub lat: f64,
+    /// Longitude in decimal degrees.
+    pub lon: f64,
+    /// Comma separated relations to embed, supported: `languages`, `neighbours`.
+    pub include: Option<String>,
+    /// Geometry detail: `none`, `low`, `medium` or `full` (default).
+    pub detail: Option<String>,
+}
@@ -1,10 +1,24 @@
+use crate::app::drivers::middlewares::auth::AuthenticatedUser;
+use crate::app::features::auth::permissions::{authorize, Action};
 use crate::error::AppError;
+use crate::utils::geojson::ResponseFormat;
+use crate::utils::pagination::{PageLinks, PageRequest};
+use crate::utils::sorting::Sort;
+use crate::utils::topojson::{build_topology, TopologyFeature};
+use crate::utils::geo::{is_valid_latitude, is_valid_longitude, polygons_contain, polygons_from_geo_json, BoundingBox, GeometryDetail};
 use super::{
-    presenters::CommonPresenter,
-    repositories::{CommonRepository, GetAllCountriesRepositoryInput}
+    presenters::{CommonPresenter, CountryRelations},
+    repositories::{
+        CommonRepository, CountrySortField, CreateCountryRepositoryInput, CreateOrganisationTypeRepositoryInput, GetAllCountriesRepositoryInput,
+        GetCountryRepositoryInput, UpdateCountryRepositoryInput, UpdateOrganisationTypeRepositoryInput,
+    }
 };
+use crate::data::models::Country;
+use std::collections::HashMap;
 use std::sync::Arc;
 use actix_web::HttpResponse;
+use serde_json::json;
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct CommonUsecase {
@@ -24,19 +38,220 @@ impl CommonUsecase {
     }
 
     pub fn fetch_all_countries(&self, params: FetchAllCountriesUsecaseInput) -> Result<HttpResponse, AppError> {
-        let countries = self.common_repo
+        params.sort.check_page(&params.page)?;
+        let mut countries = self.common_repo
             .get_all_countries(
                 GetAllCountriesRepositoryInput {
-                    limit: params.limit,
-                    offset: params.offset,
+                    sort: params.sort,
+                    page: params.page,
+                }
+            )?;
+        self.attach_geometries(&mut countries.items, params.detail)?;
+        let relations = self.load_country_relations(&countries.items, &params.includes)?;
+        let response = match params.format {
+            ResponseFormat::Json => self.common_presenter.to_multi_country_json(countries, relations, params.links),
+            ResponseFormat::GeoJson => self.common_presenter.to_multi_country_geo_json(countries, relations, params.links),
+        };
+
+        Ok(response)
+    }
+
+    /// All country shapes as one TopoJSON topology with shared borders.
+    pub fn fetch_countries_topology(&self, params: FetchCountriesTopologyUsecaseInput) -> Result<HttpResponse, AppError> {
+        if params.detail == GeometryDetail::None {
+            return Err(AppError::UnprocessableEntity(json!({ "error": "A topology cannot be built without geometry" })));
+        }
+
+        let features: Vec<TopologyFeature> = self.common_repo
+            .get_country_shapes(params.detail)?
+            .into_iter()
+            .map(|(country, geometry)| TopologyFeature {
+                id: Some(country.id.to_string()),
+                properties: json!({
+                    "name": country.name,
+                    "isoAlpha2": country.iso_alpha2,
+                    "isoAlpha3": country.iso_alpha3,
+                }),
+                geometry,
+            })
+            .collect();
+        let topology = build_topology("countries", &features, params.quantization);
+        let response = self.common_presenter
+            .to_country_topo_json(topology);
+
+        Ok(response)
+    }
+
+    /// Fetches a single country by id, ISO 3166-1 code (alpha-2 or alpha-3)
+    /// or case-insensitive name; the first given key wins.
+    pub fn fetch_country(&self, params: FetchCountryUsecaseInput) -> Result<HttpResponse, AppError> {
+        if let Some(iso_code) = &params.iso_code {
+            let valid = matches!(iso_code.len(), 2 | 3) && iso_code.chars().all(|c| c.is_ascii_alphabetic());
+            if !valid {
+                return Err(AppError::UnprocessableEntity(json!({
+                    "error": format!("'{}' is not an ISO 3166-1 alpha-2 or alpha-3 code", iso_code)
+                })));
+            }
+        }
+
+        let mut country = self.common_repo
+            .get_country(
+                GetCountryRepositoryInput {
+                    id: params.id,
+                    name: params.name,
+                    iso_code: params.iso_code,
+                }
+            )?;
+        if params.detail != GeometryDetail::Full {
+            self.attach_geometries(std::slice::from_mut(&mut country), params.detail)?;
+        }
+        let relations = self.load_country_relations(std::slice::from_ref(&country), &params.includes)?;
+        let response = self.common_presenter
+            .to_single_country_json(country, relations);
+
+        Ok(response)
+    }
+
+    /// Reverse geocoding: the country whose borders contain the point.
+    pub fn fetch_country_at(&self, params: FetchCountryAtUsecaseInput) -> Result<HttpResponse, AppError> {
+        let (lat, lon) = (params.latitude, params.longitude);
+        if !is_valid_latitude(lat) || !is_valid_longitude(lon) {
+            return Err(AppError::UnprocessableEntity(json!({
+                "error": "lat must be within [-90, 90] and lon within [-180, 180]"
+            })));
+        }
+
+        // Only countries whose bounding box holds the point can contain it.
+        let point = BoundingBox { min_lat: lat, min_lon: lon, max_lat: lat, max_lon: lon };
+        let country_id = self.common_repo
+            .get_country_geometries(&point)?
+            .into_iter()
+            .find(|(_, geo_json)| {
+                polygons_from_geo_json(geo_json).is_some_and(|polygons| polygons_contain(&polygons, lat, lon))
+            })
+            .map(|(id, _)| id)
+            .ok_or_else(|| AppError::NotFound(json!({ "error": "No country contains this point" })))?;
+
+        self.fetch_country(
+            FetchCountryUsecaseInput {
+                id: Some(country_id),
+                name: None,
+                iso_code: None,
+                includes: params.includes,
+                detail: params.detail,
+            }
+        )
+    }
+
+    pub fn create_country(
+        &self,
+        actor: &AuthenticatedUser,
+        params: CreateCountryUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageReferenceData)?;
+        let name = validate_country_name(params.name)?;
+        validate_country_geo_json(params.geo_json.as_ref())?;
+        let new_country = self.common_repo
+            .create_country(
+                CreateCountryRepositoryInput {
+                    name,
+                    geo_json: params.geo_json,
+                    flag: params.flag,
+                    capital_city: params.capital_city,
+                    description: params.description,
+                    iso_alpha2: normalize_iso_code(params.iso_alpha2, 2)?,
+                    iso_alpha3: normalize_iso_code(params.iso_alpha3, 3)?,
                 }
             )?;
         let response = self.common_presenter
-            .to_multi_country_json(countries);
+            .to_single_country_json(new_country, CountryRelations::default());
+
+        Ok(response)
+    }
+
+    pub fn update_country(
+        &self,
+        actor: &AuthenticatedUser,
+        id: Uuid,
+        params: UpdateCountryUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageReferenceData)?;
+        let name = params.name.map(validate_country_name).transpose()?;
+        validate_country_geo_json(params.geo_json.as_ref())?;
+        let params = UpdateCountryRepositoryInput {
+            name,
+            geo_json: params.geo_json,
+            flag: params.flag,
+            capital_city: params.capital_city,
+            description: params.description,
+            iso_alpha2: normalize_iso_code(params.iso_alpha2, 2)?,
+            iso_alpha3: normalize_iso_code(params.iso_alpha3, 3)?,
+        };
+        let has_changes = params.name.is_some()
+            || params.geo_json.is_some()
+            || params.flag.is_some()
+            || params.capital_city.is_some()
+            || params.description.is_some()
+            || params.iso_alpha2.is_some()
+            || params.iso_alpha3.is_some();
+
+        if !has_changes {
+            return Err(AppError::UnprocessableEntity(json!({ "error": "No fields to update" })));
+        }
+
+        let updated_country = self.common_repo.update_country(id, params)?;
+        let response = self.common_presenter
+            .to_single_country_json(updated_country, CountryRelations::default());
 
         Ok(response)
     }
 
+    pub fn delete_country(&self, actor: &AuthenticatedUser, id: Uuid) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageReferenceData)?;
+        self.common_repo.delete_country(id)?;
+        let response = self.common_presenter.to_http_res();
+
+        Ok(response)
+    }
+
+    /// Replaces the geometry of the countries with the precomputed one at the
+    /// requested level of detail, or drops it for `GeometryDetail::None`.
+    fn attach_geometries(&self, countries: &mut [Country], detail: GeometryDetail) -> Result<(), AppError> {
+        let mut geometries = match detail {
+            GeometryDetail::None => HashMap::new(),
+            _ => {
+                let ids: Vec<_> = countries.iter().map(|country| country.id).collect();
+                self.common_repo.get_country_geometries_by_ids(&ids, detail)?
+            }
+        };
+
+        for country in countries.iter_mut() {
+            country.geo_json = geometries.remove(&country.id);
+        }
+
+        Ok(())
+    }
+
+    fn load_country_relations(
+        &self,
+        countries: &[Country],
+        includes: &CountryIncludes,
+    ) -> Result<CountryRelations, AppError> {
+        let mut relations = CountryRelations::default();
+
+        let ids: Vec<_> = countries.iter().map(|country| country.id).collect();
+
+        if includes.languages {
+            relations.languages = Some(self.common_repo.get_country_languages(&ids)?);
+        }
+
+        if includes.neighbours {
+            relations.neighbours = Some(self.common_repo.get_country_neighbours(&ids)?);
+        }
+
+        Ok(relations)
+    }
+
     pub fn fetch_organisation_types(&self) -> Result<HttpResponse, AppError> {
         let org_types = self.common_repo
             .get_all_organisation_types()?;
@@ -45,9 +260,247 @@ impl CommonUsecase {
 
         Ok(response)
     }
+
+    pub fn fetch_organisation_type(&self, id: Uuid) -> Result<HttpResponse, AppError> {
+        let org_type = self.common_repo
+            .get_organisation_type(&id)?;
+        let response = self.common_presenter
+            .to_single_organization_type_json(org_type);
+
+        Ok(response)
+    }
+
+    pub fn create_organisation_type(
+        &self,
+        actor: &AuthenticatedUser,
+        params: CreateOrganisationTypeUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageReferenceData)?;
+        let new_org_type = self.common_repo
+            .create_organisation_type(
+                CreateOrganisationTypeRepositoryInput {
+                    org_type: validate_organisation_type_name(params.org_type)?,
+                    color: params.color.map(normalize_hex_color).transpose()?,
+                }
+            )?;
+        let response = self.common_presenter
+            .to_single_organization_type_json(new_org_type);
+
+        Ok(response)
+    }
+
+    pub fn update_organisation_type(
+        &self,
+        actor: &AuthenticatedUser,
+        id: Uuid,
+        params: UpdateOrganisationTypeUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageReferenceData)?;
+        if params.org_type.is_none() && params.color.is_none() {
+            return Err(AppError::UnprocessableEntity(json!({ "error": "No fields to update" })));
+        }
+
+        let updated_org_type = self.common_repo
+            .update_organisation_type(
+                id,
+                UpdateOrganisationTypeRepositoryInput {
+                    org_type: params.org_type.map(validate_organisation_type_name).transpose()?,
+                    color: params.color.map(normalize_hex_color).transpose()?,
+                }
+            )?;
+        let response = self.common_presenter
+            .to_single_organization_type_json(updated_org_type);
+
+        Ok(response)
+    }
+
+    pub fn delete_organisation_type(&self, actor: &AuthenticatedUser, id: Uuid) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageReferenceData)?;
+        self.common_repo.delete_organisation_type(id)?;
+        let response = self.common_presenter.to_http_res();
+
+        Ok(response)
+    }
 }
 
 pub struct FetchAllCountriesUsecaseInput {
-    pub limit: i64,
-    pub offset: i64,
+    pub sort: Sort<CountrySortField>,
+    pub page: PageRequest,
+    pub includes: CountryIncludes,
+    pub format: ResponseFormat,
+    pub detail: GeometryDetail,
+    pub links: PageLinks,
+}
+
+pub struct FetchCountriesTopologyUsecaseInput {
+    pub quantization: u32,
+    pub detail: GeometryDetail,
+}
+
+pub struct FetchCountryUsecaseInput {
+    pub id: Option<Uuid>,
+    pub name: Option<String>,
+    pub iso_code: Option<String>,
+    pub includes: CountryIncludes,
+    pub detail: GeometryDetail,
+}
+
+pub struct FetchCountryAtUsecaseInput {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub includes: CountryIncludes,
+    pub detail: GeometryDetail,
+}
+
+pub struct CreateCountryUsecaseInput {
+    pub name: String,
+    pub geo_json: Option<serde_json::Value>,
+    pub flag: Option<String>,
+    pub capital_city: Option<String>,
+    pub description: Option<String>,
+    pub iso_alpha2: Option<String>,
+    pub iso_alpha3: Option<String>,
+}
+
+pub struct UpdateCountryUsecaseInput {
+    pub name: Option<String>,
+    pub geo_json: Option<serde_json::Value>,
+    pub flag: Option<String>,
+    pub capital_city: Option<String>,
+    pub description: Option<String>,
+    pub iso_alpha2: Option<String>,
+    pub iso_alpha3: Option<String>,
+}
+
+pub struct CreateOrganisationTypeUsecaseInput {
+    pub org_type: String,
+    pub color: Option<String>,
+}
+
+pub struct UpdateOrganisationTypeUsecaseInput {
+    pub org_type: Option<String>,
+    pub color: Option<String>,
+}
+
+fn validate_organisation_type_name(org_type: String) -> Result<String, AppError> {
+    let org_type = org_type.trim();
+
+    if org_type.is_empty() {
+        return Err(AppError::UnprocessableEntity(json!({ "error": "Organisation type must not be empty" })));
+    }
+
+    Ok(org_type.to_string())
+}
+
+/// Accepts `#rgb` and `#rrggbb` colours and stores them lower-cased.
+fn normalize_hex_color(color: String) -> Result<String, AppError> {
+    let color = color.trim().to_ascii_lowercase();
+    let valid = color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()));
+
+    if !valid {
+        return Err(AppError::UnprocessableEntity(json!({
+            "error": format!("'{}' is not a hex colour, expected #rgb or #rrggbb", color)
+        })));
+    }
+
+    Ok(color)
+}
+
+fn validate_country_name(name: String) -> Result<String, AppError> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(AppError::UnprocessableEntity(json!({ "error": "Country name must not be empty" })));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Upper-cases an ISO 3166-1 code and checks it has the expected number of letters.
+fn normalize_iso_code(code: Option<String>, len: usize) -> Result<Option<String>, AppError> {
+    let Some(code) = code else {
+        return Ok(None);
+    };
+    let code = code.trim().to_ascii_uppercase();
+
+    if code.len() != len || !code.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(AppError::UnprocessableEntity(json!({
+            "error": format!("'{}' is not an ISO 3166-1 alpha-{} code", code, len)
+        })));
+    }
+
+    Ok(Some(code))
+}
+
+/// Country borders are stored as a bare GeoJSON geometry.
+fn validate_country_geo_json(geo_json: Option<&serde_json::Value>) -> Result<(), AppError> {
+    let Some(geo_json) = geo_json else {
+        return Ok(());
+    };
+    let geometry_type = geo_json.get("type").and_then(|value| value.as_str());
+    let has_coordinates = geo_json.get("coordinates").is_some_and(|value| value.is_array());
+
+    if !matches!(geometry_type, Some("Polygon" | "MultiPolygon")) || !has_coordinates {
+        return Err(AppError::UnprocessableEntity(json!({
+            "error": "geo_json must be a GeoJSON Polygon or MultiPolygon geometry"
+        })));
+    }
+
+    Ok(())
+}
+
+/// Optional relations embedded into country responses, requested as a
+/// comma separated `include` query parameter, e.g. `include=languages`.
+#[derive(Default)]
+pub struct CountryIncludes {
+    pub languages: bool,
+    pub neighbours: bool,
+}
+impl CountryIncludes {
+    pub fn parse(include: Option<&str>) -> Result<Self, AppError> {
+        let mut includes = Self::default();
+
+        for item in include.unwrap_or_default().split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            match item {
+                "languages" => includes.languages = true,
+                "neighbours" => includes.neighbours = true,
+                unknown => {
+                    return Err(AppError::UnprocessableEntity(json!({
+                        "error": format!("Unknown include '{}'", unknown),
+                        "allowed": ["languages", "neighbours"]
+                    })));
+                }
+            }
+        }
+
+        Ok(includes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_colours_are_trimmed_and_lower_cased() {
+        assert_eq!(normalize_hex_color(" #A1B2C3 ".to_string()).unwrap(), "#a1b2c3");
+        assert_eq!(normalize_hex_color("#FFF".to_string()).unwrap(), "#fff");
+
+        for color in ["a1b2c3", "#abcd", "#ggg", "#", "red"] {
+            assert!(matches!(normalize_hex_color(color.to_string()), Err(AppError::UnprocessableEntity(_))), "{}", color);
+        }
+    }
+
+    #[test]
+    fn iso_codes_are_upper_cased_and_sized() {
+        assert_eq!(normalize_iso_code(Some(" de ".to_string()), 2).unwrap(), Some("DE".to_string()));
+        assert_eq!(normalize_iso_code(Some("deu".to_string()), 3).unwrap(), Some("DEU".to_string()));
+        assert_eq!(normalize_iso_code(None, 2).unwrap(), None);
+
+        for (code, len) in [("deu", 2), ("de", 3), ("d1", 2), ("dé", 2)] {
+            assert!(matches!(normalize_iso_code(Some(code.to_string()), len), Err(AppError::UnprocessableEntity(_))), "{}", code);
+        }
+    }
 }
@@ -2,11 +2,12 @@ use super::{
     requests::{CreateCountryConnectionRequest, CountryConnectionsListQueryParams, UpdateCountryConnectionRequest},
     usecases::{CreateCountryConnectionUsecaseInput, FetchCountryConnectionsUsecaseInput, UpdateCountryConnectionUsecaseInput},
 };
-use crate::app::drivers::middlewares::state::AppState;
+use crate::app::drivers::middlewares::{auth::AuthenticatedUser, state::AppState};
 use crate::error::AppError;
+use crate::utils::pagination::{PageLinks, PageRequest};
+use crate::utils::sorting::Sort;
 use actix_web::{web::{Data, Json, Path, Query}, HttpRequest, HttpResponse};
 use uuid::Uuid;
-use std::cmp::min;
 
 #[utoipa::path(
     get,
@@ -14,18 +15,19 @@ use std::cmp::min;
     context_path = "/api",
     params(CountryConnectionsListQueryParams),
     responses(
-        (status = 200, description = "Country connections list response", body = super::presenters::MultipleCountryConnectionsResponse),
+        (status = 200, description = "Country connections list response", body = super::presenters::MultipleCountryConnectionsResponse, headers(
+            ("Link" = String, description = "RFC 8288 `first`, `prev`, `next` and `last` page links")
+        )),
+        (status = 422, description = "Unknown sort field, invalid cursor or page size out of range", body = AppError),
         (status = 500, description = "Internal server error", body = AppError)
     ),
     tag = "CountryConnection"
 )]
 pub async fn list(
     state: Data<AppState>,
+    req: HttpRequest,
     query: Query<CountryConnectionsListQueryParams>
 ) -> Result<HttpResponse, AppError> {
-    let offset = min(query.offset.unwrap_or(0), 150);
-    let limit = query.limit.unwrap_or(20);
-
     state
         .di_container
         .country_connection_usecase
@@ -34,8 +36,9 @@ pub async fn list(
                 embassy_org_id: query.embassy_org_id,
                 consulate_org_id: query.consulate_org_id,
                 location_country_id: query.location_country_id,
-                limit,
-                offset,
+                sort: Sort::parse(query.sort.as_deref())?,
+                page: PageRequest::parse(query.limit, query.offset, query.cursor.as_deref())?,
+                links: PageLinks::from_request(&req),
             }
         )
 }
@@ -73,19 +76,24 @@ pub async fn fetch(
     responses(
         (status = 200, description = "Country connections list response", body = super::presenters::CountryConnectionContent),
         (status = 400, description = "Bad request", body = AppError),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
         (status = 500, description = "Internal server error", body = AppError)
     ),
+    security(("bearer_auth" = [])),
     tag = "CountryConnection"
 )]
 pub async fn create(
     state: Data<AppState>,
     _req: HttpRequest,
+    user: AuthenticatedUser,
     form: Json<CreateCountryConnectionRequest>
 ) -> Result<HttpResponse, AppError> {
     state
         .di_container
         .country_connection_usecase
         .create_country_connection(
+            &user,
             CreateCountryConnectionUsecaseInput {
                 embassy_org_id: form.embassy_org_id,
                 consulate_org_id: form.consulate_org_id,
@@ -106,13 +114,17 @@ pub async fn create(
     responses(
         (status = 200, description = "Country connections list response", body = super::presenters::CountryConnectionContent),
         (status = 400, description = "Bad request", body = AppError),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
         (status = 500, description = "Internal server error", body = AppError)
     ),
+    security(("bearer_auth" = [])),
     tag = "CountryConnection"
 )]
 pub async fn update(
     state: Data<AppState>,
     _req: HttpRequest,
+    user: AuthenticatedUser,
     id: Path<Uuid>,
     form: Json<UpdateCountryConnectionRequest>
 ) -> Result<HttpResponse, AppError> {
@@ -120,6 +132,7 @@ pub async fn update(
         .di_container
         .country_connection_usecase
         .update_country_connection(
+            &user,
             id.into_inner(),
             UpdateCountryConnectionUsecaseInput {
                 embassy_org_id: form.embassy_org_id,
@@ -140,17 +153,21 @@ pub async fn update(
     responses(
         (status = 200, description = "Country connection deleted successfully"),
         (status = 400, description = "Bad request", body = AppError),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
         (status = 500, description = "Internal server error", body = AppError)
     ),
+    security(("bearer_auth" = [])),
     tag = "CountryConnection"
 )]
 pub async fn delete(
     state: Data<AppState>,
     _req: HttpRequest,
+    user: AuthenticatedUser,
     id: Path<Uuid>
 ) -> Result<HttpResponse, AppError> {
     state
         .di_container
         .country_connection_usecase
-        .delete_country_connection(id.into_inner())
+        .delete_country_connection(&user, id.into_inner())
 }
@@ -3,7 +3,10 @@ use crate::{
     data::models::Country
 };
 use crate::error::*;
+use crate::utils::pagination::PageRequest;
+use crate::utils::sorting::{Direction, Sort, SortField};
 use serde::{Deserialize, Serialize};
+use diesel::pg::Pg;
 use diesel::prelude::*;
 use uuid::Uuid;
 
@@ -20,6 +23,23 @@ pub struct CountryConnection {
     pub common_info: Option<String>,
 }
 
+/// Columns the country connection list can be sorted by, besides the
+/// default order by id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountryConnectionSortField {
+    EmbassyOrg,
+    ConsulateOrg,
+    LocationCountry,
+}
+
+impl SortField for CountryConnectionSortField {
+    const ALLOWED: &'static [(&'static str, Self)] = &[
+        ("embassy_org_id", Self::EmbassyOrg),
+        ("consulate_org_id", Self::ConsulateOrg),
+        ("location_country_id", Self::LocationCountry),
+    ];
+}
+
 #[derive(Insertable, Clone)]
 #[diesel(table_name = countries_connections)]
 pub struct CreateCountryConnection {
@@ -106,9 +126,52 @@ impl CountryConnection {
         embassy_org_id: Option<Uuid>,
         consulate_org_id: Option<Uuid>,
         location_country_id: Option<Uuid>,
-        limit: i64,
-        offset: i64,
+        sort: &Sort<CountryConnectionSortField>,
+        page: &PageRequest,
     ) -> Result<Vec<Self>, AppError> {
+        let mut query = Self::filtered(embassy_org_id, consulate_org_id, location_country_id);
+        for &(field, direction) in &sort.fields {
+            query = match (field, direction) {
+                (CountryConnectionSortField::EmbassyOrg, Direction::Asc) => query.then_order_by(countries_connections::embassy_org_id.asc()),
+                (CountryConnectionSortField::EmbassyOrg, Direction::Desc) => query.then_order_by(countries_connections::embassy_org_id.desc()),
+                (CountryConnectionSortField::ConsulateOrg, Direction::Asc) => query.then_order_by(countries_connections::consulate_org_id.asc()),
+                (CountryConnectionSortField::ConsulateOrg, Direction::Desc) => query.then_order_by(countries_connections::consulate_org_id.desc()),
+                (CountryConnectionSortField::LocationCountry, Direction::Asc) => query.then_order_by(countries_connections::location_country_id.asc()),
+                (CountryConnectionSortField::LocationCountry, Direction::Desc) => query.then_order_by(countries_connections::location_country_id.desc()),
+            };
+        }
+        let mut query = query
+            .then_order_by(countries_connections::id.asc())
+            .limit(page.fetch_limit());
+        query = match page {
+            PageRequest::Offset { offset, .. } => query.offset(*offset),
+            PageRequest::Cursor { after, .. } => query.filter(countries_connections::id.gt(after.id)),
+        };
+        let result = query
+            .select(CountryConnection::as_select())
+            .load::<CountryConnection>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn count_with_filters(
+        conn: &mut PgConnection,
+        embassy_org_id: Option<Uuid>,
+        consulate_org_id: Option<Uuid>,
+        location_country_id: Option<Uuid>,
+    ) -> Result<i64, AppError> {
+        let result = Self::filtered(embassy_org_id, consulate_org_id, location_country_id)
+            .count()
+            .get_result::<i64>(conn)?;
+
+        Ok(result)
+    }
+
+    fn filtered(
+        embassy_org_id: Option<Uuid>,
+        consulate_org_id: Option<Uuid>,
+        location_country_id: Option<Uuid>,
+    ) -> countries_connections::BoxedQuery<'static, Pg> {
         let mut query = countries_connections::table.into_boxed();
 
         if let Some(embassy_id) = embassy_org_id {
@@ -123,12 +186,6 @@ impl CountryConnection {
             query = query.filter(countries_connections::location_country_id.eq(country_id));
         }
 
-        let result = query
-            .limit(limit)
-            .offset(offset)
-            .select(CountryConnection::as_select())
-            .load::<CountryConnection>(conn)?;
-
-        Ok(result)
+        query
     }
 }
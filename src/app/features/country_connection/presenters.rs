@@ -1,4 +1,5 @@
 use super::entities::CountryConnection;
+use crate::utils::pagination::{Page, PageLinks};
 use serde::{Deserialize, Serialize};
 use actix_web::HttpResponse;
 use utoipa::ToSchema;
@@ -7,7 +8,7 @@ use uuid::Uuid;
 pub trait CountryConnectionPresenter: Send + Sync + 'static {
     fn to_http_res(&self) -> HttpResponse;
     fn to_single_json(&self, item: CountryConnection) -> HttpResponse;
-    fn to_multi_json(&self, items: Vec<CountryConnection>) -> HttpResponse;
+    fn to_multi_json(&self, page: Page<CountryConnection>, links: PageLinks) -> HttpResponse;
 }
 
 #[derive(Deserialize, Serialize, ToSchema)]
@@ -33,22 +34,30 @@ impl From<CountryConnection> for CountryConnectionContent {
 }
 
 #[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct MultipleCountryConnectionsResponse {
     pub items: Vec<CountryConnectionContent>,
+    /// Number of country connections matching the filters across all pages.
     pub total: i64,
+    pub limit: i64,
+    /// `null` for pages requested with a cursor.
+    pub offset: Option<i64>,
+    pub has_more: bool,
+    /// Pass as `cursor` to fetch the next page; `null` on the last page.
+    pub next_cursor: Option<String>,
 }
 
-impl From<Vec<CountryConnection>> for MultipleCountryConnectionsResponse {
-    fn from(items: Vec<CountryConnection>) -> Self {
-        let response_items: Vec<CountryConnectionContent> = items
-            .into_iter()
-            .map(CountryConnectionContent::from)
-            .collect();
-        let total = response_items.len() as i64;
+impl From<Page<CountryConnection>> for MultipleCountryConnectionsResponse {
+    fn from(page: Page<CountryConnection>) -> Self {
+        let page = page.map(CountryConnectionContent::from);
 
         Self {
-            items: response_items,
-            total: total,
+            items: page.items,
+            total: page.total,
+            limit: page.limit,
+            offset: page.offset,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
         }
     }
 }
@@ -71,9 +80,10 @@ impl CountryConnectionPresenter for CountryConnectionPresenterImpl {
         HttpResponse::Ok().json(response_content)
     }
 
-    fn to_multi_json(&self, items: Vec<CountryConnection>) -> HttpResponse {
-        let response_content = MultipleCountryConnectionsResponse::from(items);
+    fn to_multi_json(&self, page: Page<CountryConnection>, links: PageLinks) -> HttpResponse {
+        let mut response = links.ok(&page);
+        let response_content = MultipleCountryConnectionsResponse::from(page);
 
-        HttpResponse::Ok().json(response_content)
+        response.json(response_content)
     }
 }
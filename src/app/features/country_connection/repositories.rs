@@ -1,13 +1,15 @@
-use super::entities::{CountryConnection, CreateCountryConnection, UpdateCountryConnection};
+use super::entities::{CountryConnection, CountryConnectionSortField, CreateCountryConnection, UpdateCountryConnection};
 use crate::error::AppError;
 use crate::utils::db::DbPool;
+use crate::utils::pagination::{Cursor, Page, PageRequest};
+use crate::utils::sorting::Sort;
 use uuid::Uuid;
 
 pub trait CountryConnectionRepository: Send + Sync + 'static {
     fn fetch_country_connections(
         &self,
         params: FetchCountryConnectionsRepositoryInput
-    ) -> Result<Vec<CountryConnection>, AppError>;
+    ) -> Result<Page<CountryConnection>, AppError>;
 
     fn fetch_country_connection(
         &self,
@@ -42,18 +44,27 @@ impl CountryConnectionRepositoryImpl {
 }
 
 impl CountryConnectionRepository for CountryConnectionRepositoryImpl {
-    fn fetch_country_connections(&self, params: FetchCountryConnectionsRepositoryInput) -> Result<Vec<CountryConnection>, AppError> {
+    fn fetch_country_connections(&self, params: FetchCountryConnectionsRepositoryInput) -> Result<Page<CountryConnection>, AppError> {
         let connection = &mut self.pool.get()?;
+        let total = CountryConnection::count_with_filters(
+            connection,
+            params.embassy_org_id,
+            params.consulate_org_id,
+            params.location_country_id,
+        )?;
         let country_connections = CountryConnection::fetch_with_filters(
             connection,
             params.embassy_org_id,
             params.consulate_org_id,
             params.location_country_id,
-            params.limit,
-            params.offset,
+            &params.sort,
+            &params.page,
         )?;
+        let page = Page::new(country_connections, total, &params.page, |country_connection| {
+            Cursor::new(None, country_connection.id)
+        });
 
-        Ok(country_connections)
+        Ok(if params.sort.is_default() { page } else { page.without_cursor() })
     }
 
     fn create_country_connection(&self, params: CreateCountryConnectionRepositoryInput) -> Result<CountryConnection, AppError> {
@@ -120,8 +131,8 @@ pub struct FetchCountryConnectionsRepositoryInput {
     pub embassy_org_id: Option<Uuid>,
     pub consulate_org_id: Option<Uuid>,
     pub location_country_id: Option<Uuid>,
-    pub limit: i64,
-    pub offset: i64,
+    pub sort: Sort<CountryConnectionSortField>,
+    pub page: PageRequest,
 }
 
 pub struct CreateCountryConnectionRepositoryInput {
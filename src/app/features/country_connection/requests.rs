@@ -23,6 +23,16 @@ pub struct CountryConnectionsListQueryParams {
     pub embassy_org_id: Option<Uuid>,
     pub consulate_org_id: Option<Uuid>,
     pub location_country_id: Option<Uuid>,
+    /// Page size, default 20, at most 100.
     pub limit: Option<i64>,
+    /// Rows to skip; cannot be combined with `cursor`.
     pub offset: Option<i64>,
+    /// `nextCursor` of the previous page, to continue after it. Only
+    /// available in the default order.
+    pub cursor: Option<String>,
+    /// Comma separated `embassy_org_id`, `consulate_org_id` or
+    /// `location_country_id`, each optionally prefixed with `-` for
+    /// descending order; default is by id.
+    #[param(example = "location_country_id,-embassy_org_id")]
+    pub sort: Option<String>,
 }
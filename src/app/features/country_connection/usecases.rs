@@ -1,8 +1,13 @@
 use super::{
+    entities::CountryConnectionSortField,
     presenters::CountryConnectionPresenter,
     repositories::{UpdateCountryConnectionRepositoryInput, CountryConnectionRepository, CreateCountryConnectionRepositoryInput, FetchCountryConnectionsRepositoryInput},
 };
 use crate::error::AppError;
+use crate::utils::pagination::{PageLinks, PageRequest};
+use crate::utils::sorting::Sort;
+use crate::app::drivers::middlewares::auth::AuthenticatedUser;
+use crate::app::features::auth::permissions::{authorize, Action};
 use std::sync::Arc;
 use actix_web::HttpResponse;
 use uuid::Uuid;
@@ -39,25 +44,28 @@ impl CountryConnectionUsecase {
         &self,
         params: FetchCountryConnectionsUsecaseInput,
     ) -> Result<HttpResponse, AppError> {
+        params.sort.check_page(&params.page)?;
         let country_connections = self.country_connection_repo
             .fetch_country_connections(
                 FetchCountryConnectionsRepositoryInput {
                     embassy_org_id: params.embassy_org_id,
                     consulate_org_id: params.consulate_org_id,
                     location_country_id: params.location_country_id,
-                    limit: params.limit,
-                    offset: params.offset,
+                    sort: params.sort,
+                    page: params.page,
                 }
             )?;
-        let response = self.country_connection_presenter.to_multi_json(country_connections);
+        let response = self.country_connection_presenter.to_multi_json(country_connections, params.links);
 
         Ok(response)
     }
 
     pub fn create_country_connection(
         &self,
+        actor: &AuthenticatedUser,
         params: CreateCountryConnectionUsecaseInput,
     ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::CreateCountryConnection)?;
         let new_country_connection = self.country_connection_repo
             .create_country_connection(
                 CreateCountryConnectionRepositoryInput {
@@ -74,9 +82,11 @@ impl CountryConnectionUsecase {
 
     pub fn update_country_connection(
         &self,
+        actor: &AuthenticatedUser,
         id: Uuid,
         params: UpdateCountryConnectionUsecaseInput,
     ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::UpdateCountryConnection)?;
         let updated_country_connection = self.country_connection_repo
             .update_country_connection(
                 id,
@@ -92,7 +102,8 @@ impl CountryConnectionUsecase {
         Ok(response)
     }
 
-    pub fn delete_country_connection(&self, id: Uuid) -> Result<HttpResponse, AppError> {
+    pub fn delete_country_connection(&self, actor: &AuthenticatedUser, id: Uuid) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::DeleteCountryConnection)?;
         self.country_connection_repo
             .delete_country_connection(id)?;
         let response = self.country_connection_presenter.to_http_res();
@@ -105,8 +116,9 @@ pub struct FetchCountryConnectionsUsecaseInput {
     pub embassy_org_id: Option<Uuid>,
     pub consulate_org_id: Option<Uuid>,
     pub location_country_id: Option<Uuid>,
-    pub limit: i64,
-    pub offset: i64,
+    pub sort: Sort<CountryConnectionSortField>,
+    pub page: PageRequest,
+    pub links: PageLinks,
 }
 
 pub struct CreateCountryConnectionUsecaseInput {
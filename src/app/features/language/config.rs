@@ -0,0 +1,22 @@
+use super::controllers::{attach, create, delete, detach, fetch, list, update};
+use actix_web::{web, web::ServiceConfig};
+
+pub fn configure_services(cfg: &mut ServiceConfig) {
+    cfg.service(
+        web::scope("/language")
+            .route("/create", web::post()
+                .to(create))
+            .route("/list", web::get()
+                .to(list))
+            .route("/delete/{id}", web::delete()
+                .to(delete))
+            .route("/update/{id}", web::put()
+                .to(update))
+            .route("/fetch/{id}", web::get()
+                .to(fetch))
+            .route("/attach", web::post()
+                .to(attach))
+            .route("/detach", web::post()
+                .to(detach))
+    );
+}
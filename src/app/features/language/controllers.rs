@@ -0,0 +1,228 @@
+use super::{
+    requests::{CountryLanguageRequest, CreateLanguageRequest, LanguagesListQueryParams, UpdateLanguageRequest},
+    usecases::{CountryLanguageUsecaseInput, CreateLanguageUsecaseInput, FetchLanguagesUsecaseInput, UpdateLanguageUsecaseInput},
+};
+use crate::app::drivers::middlewares::{auth::AuthenticatedUser, state::AppState};
+use crate::error::AppError;
+use crate::utils::pagination::{PageLinks, PageRequest};
+use actix_web::{web::{Data, Json, Path, Query}, HttpRequest, HttpResponse};
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/language/list",
+    context_path = "/api",
+    params(LanguagesListQueryParams),
+    responses(
+        (status = 200, description = "Languages list response, ordered by name", body = super::presenters::MultipleLanguagesResponse, headers(
+            ("Link" = String, description = "RFC 8288 `first`, `prev`, `next` and `last` page links")
+        )),
+        (status = 422, description = "Invalid cursor or page size out of range", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Language"
+)]
+pub async fn list(
+    state: Data<AppState>,
+    req: HttpRequest,
+    query: Query<LanguagesListQueryParams>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .language_usecase
+        .fetch_languages(
+            FetchLanguagesUsecaseInput {
+                page: PageRequest::parse(query.limit, query.offset, query.cursor.as_deref())?,
+                links: PageLinks::from_request(&req),
+            }
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/language/fetch/{id}",
+    context_path = "/api",
+    params(
+        ("id" = Uuid, Path, description = "Language ID to fetch")
+    ),
+    responses(
+        (status = 200, description = "Language fetched successfully", body = super::presenters::LanguageContent),
+        (status = 404, description = "Language not found", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Language"
+)]
+pub async fn fetch(
+    state: Data<AppState>,
+    id: Path<Uuid>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .language_usecase
+        .fetch_language(id.into_inner())
+}
+
+#[utoipa::path(
+    post,
+    path = "/language/create",
+    context_path = "/api",
+    request_body = CreateLanguageRequest,
+    responses(
+        (status = 200, description = "Language created successfully", body = super::presenters::LanguageContent),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 422, description = "Empty name or symbol already in use", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Language"
+)]
+pub async fn create(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    form: Json<CreateLanguageRequest>
+) -> Result<HttpResponse, AppError> {
+    let form = form.into_inner();
+
+    state
+        .di_container
+        .language_usecase
+        .create_language(
+            &user,
+            CreateLanguageUsecaseInput {
+                name: form.name,
+                symbol: form.symbol,
+            }
+        )
+}
+
+#[utoipa::path(
+    put,
+    path = "/language/update/{id}",
+    context_path = "/api",
+    request_body = UpdateLanguageRequest,
+    params(
+        ("id" = Uuid, Path, description = "Language ID to update")
+    ),
+    responses(
+        (status = 200, description = "Language updated successfully", body = super::presenters::LanguageContent),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 404, description = "Language not found", body = AppError),
+        (status = 422, description = "No fields to update, empty name or symbol already in use", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Language"
+)]
+pub async fn update(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    form: Json<UpdateLanguageRequest>
+) -> Result<HttpResponse, AppError> {
+    let form = form.into_inner();
+
+    state
+        .di_container
+        .language_usecase
+        .update_language(
+            &user,
+            id.into_inner(),
+            UpdateLanguageUsecaseInput {
+                name: form.name,
+                symbol: form.symbol,
+            }
+        )
+}
+
+#[utoipa::path(
+    delete,
+    path = "/language/delete/{id}",
+    context_path = "/api",
+    params(
+        ("id" = Uuid, Path, description = "Language ID to delete")
+    ),
+    responses(
+        (status = 200, description = "Language and its country relations deleted successfully"),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Language"
+)]
+pub async fn delete(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .language_usecase
+        .delete_language(&user, id.into_inner())
+}
+
+#[utoipa::path(
+    post,
+    path = "/language/attach",
+    context_path = "/api",
+    request_body = CountryLanguageRequest,
+    responses(
+        (status = 200, description = "Language attached to the country"),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 422, description = "Country or language does not exist", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Language"
+)]
+pub async fn attach(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    form: Json<CountryLanguageRequest>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .language_usecase
+        .attach_to_country(
+            &user,
+            CountryLanguageUsecaseInput {
+                country_id: form.country_id,
+                language_id: form.language_id,
+            }
+        )
+}
+
+#[utoipa::path(
+    post,
+    path = "/language/detach",
+    context_path = "/api",
+    request_body = CountryLanguageRequest,
+    responses(
+        (status = 200, description = "Language detached from the country"),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
+        (status = 404, description = "Language is not attached to the country", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Language"
+)]
+pub async fn detach(
+    state: Data<AppState>,
+    user: AuthenticatedUser,
+    form: Json<CountryLanguageRequest>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .language_usecase
+        .detach_from_country(
+            &user,
+            CountryLanguageUsecaseInput {
+                country_id: form.country_id,
+                language_id: form.language_id,
+            }
+        )
+}
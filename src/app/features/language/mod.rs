@@ -0,0 +1,6 @@
+pub mod presenters;
+pub mod controllers;
+pub mod repositories;
+pub mod usecases;
+pub mod requests;
+pub mod config;
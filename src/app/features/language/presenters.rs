@@ -0,0 +1,85 @@
+use crate::data::models::Language;
+use crate::utils::pagination::{Page, PageLinks};
+use serde::{Deserialize, Serialize};
+use actix_web::HttpResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub trait LanguagePresenter: Send + Sync + 'static {
+    fn to_http_res(&self) -> HttpResponse;
+    fn to_single_json(&self, item: Language) -> HttpResponse;
+    fn to_multi_json(&self, page: Page<Language>, links: PageLinks) -> HttpResponse;
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageContent {
+    pub id: Uuid,
+    pub name: String,
+    pub symbol: Option<String>,
+}
+
+impl From<Language> for LanguageContent {
+    fn from(language: Language) -> Self {
+        Self {
+            id: language.id,
+            name: language.name,
+            symbol: language.symbol,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MultipleLanguagesResponse {
+    pub items: Vec<LanguageContent>,
+    /// Number of languages across all pages.
+    pub total: i64,
+    pub limit: i64,
+    /// `null` for pages requested with a cursor.
+    pub offset: Option<i64>,
+    pub has_more: bool,
+    /// Pass as `cursor` to fetch the next page; `null` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+impl From<Page<Language>> for MultipleLanguagesResponse {
+    fn from(page: Page<Language>) -> Self {
+        let page = page.map(LanguageContent::from);
+
+        Self {
+            items: page.items,
+            total: page.total,
+            limit: page.limit,
+            offset: page.offset,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LanguagePresenterImpl {}
+impl LanguagePresenterImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+impl LanguagePresenter for LanguagePresenterImpl {
+    fn to_http_res(&self) -> HttpResponse {
+        HttpResponse::Ok().json("OK")
+    }
+
+    fn to_single_json(&self, item: Language) -> HttpResponse {
+        let response_content = LanguageContent::from(item);
+
+        HttpResponse::Ok().json(response_content)
+    }
+
+    fn to_multi_json(&self, page: Page<Language>, links: PageLinks) -> HttpResponse {
+        let mut response = links.ok(&page);
+        let response_content = MultipleLanguagesResponse::from(page);
+
+        response.json(response_content)
+    }
+}
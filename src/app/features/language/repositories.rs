@@ -0,0 +1,152 @@
+use crate::data::models::{CountryToLanguage, CreateLanguage, Language, UpdateLanguage};
+use crate::error::AppError;
+use crate::utils::db::DbPool;
+use crate::utils::pagination::{Cursor, Page, PageRequest};
+use uuid::Uuid;
+
+pub trait LanguageRepository: Send + Sync + 'static {
+    fn fetch_languages(
+        &self,
+        params: FetchLanguagesRepositoryInput
+    ) -> Result<Page<Language>, AppError>;
+
+    fn fetch_language(
+        &self,
+        id: Uuid
+    ) -> Result<Language, AppError>;
+
+    fn create_language(
+        &self,
+        params: CreateLanguageRepositoryInput
+    ) -> Result<Language, AppError>;
+
+    fn update_language(
+        &self,
+        id: Uuid,
+        params: UpdateLanguageRepositoryInput
+    ) -> Result<Language, AppError>;
+
+    fn delete_language(
+        &self,
+        id: Uuid
+    ) -> Result<(), AppError>;
+
+    fn attach_to_country(
+        &self,
+        params: CountryLanguageRepositoryInput
+    ) -> Result<(), AppError>;
+
+    fn detach_from_country(
+        &self,
+        params: CountryLanguageRepositoryInput
+    ) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct LanguageRepositoryImpl {
+    pool: DbPool
+}
+impl LanguageRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl LanguageRepository for LanguageRepositoryImpl {
+    fn fetch_languages(&self, params: FetchLanguagesRepositoryInput) -> Result<Page<Language>, AppError> {
+        let connection = &mut self.pool.get()?;
+        let total = Language::count(connection)?;
+        let languages = Language::get_all(connection, &params.page)?;
+        let page = Page::new(languages, total, &params.page, |language| {
+            Cursor::new(Some(language.name.clone()), language.id)
+        });
+
+        Ok(page)
+    }
+
+    fn fetch_language(&self, id: Uuid) -> Result<Language, AppError> {
+        let connection = &mut self.pool.get()?;
+        let language = Language::get_by_id(connection, &id)?;
+
+        Ok(language)
+    }
+
+    fn create_language(&self, params: CreateLanguageRepositoryInput) -> Result<Language, AppError> {
+        let connection = &mut self.pool.get()?;
+        let new_language = Language::create(
+            connection,
+            &CreateLanguage {
+                name: params.name,
+                symbol: params.symbol,
+            }
+        )?;
+
+        Ok(new_language)
+    }
+
+    fn update_language(&self, id: Uuid, params: UpdateLanguageRepositoryInput) -> Result<Language, AppError> {
+        let connection = &mut self.pool.get()?;
+        let updated_language = Language::update(
+            connection,
+            id,
+            &UpdateLanguage {
+                name: params.name,
+                symbol: params.symbol,
+            }
+        )?;
+
+        Ok(updated_language)
+    }
+
+    fn delete_language(&self, id: Uuid) -> Result<(), AppError> {
+        let connection = &mut self.pool.get()?;
+        Language::delete(connection, id)?;
+
+        Ok(())
+    }
+
+    fn attach_to_country(&self, params: CountryLanguageRepositoryInput) -> Result<(), AppError> {
+        let connection = &mut self.pool.get()?;
+        CountryToLanguage::attach(
+            connection,
+            &CountryToLanguage {
+                country_id: params.country_id,
+                language_id: params.language_id,
+            }
+        )?;
+
+        Ok(())
+    }
+
+    fn detach_from_country(&self, params: CountryLanguageRepositoryInput) -> Result<(), AppError> {
+        let connection = &mut self.pool.get()?;
+        CountryToLanguage::detach(
+            connection,
+            &CountryToLanguage {
+                country_id: params.country_id,
+                language_id: params.language_id,
+            }
+        )?;
+
+        Ok(())
+    }
+}
+
+pub struct FetchLanguagesRepositoryInput {
+    pub page: PageRequest,
+}
+
+pub struct CreateLanguageRepositoryInput {
+    pub name: String,
+    pub symbol: Option<String>,
+}
+
+pub struct UpdateLanguageRepositoryInput {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+}
+
+pub struct CountryLanguageRepositoryInput {
+    pub country_id: Uuid,
+    pub language_id: Uuid,
+}
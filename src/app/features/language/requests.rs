@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateLanguageRequest {
+    pub name: String,
+    pub symbol: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateLanguageRequest {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CountryLanguageRequest {
+    pub country_id: Uuid,
+    pub language_id: Uuid,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, IntoParams, Debug)]
+pub struct LanguagesListQueryParams {
+    /// Page size, default 20, at most 100.
+    pub limit: Option<i64>,
+    /// Rows to skip; cannot be combined with `cursor`.
+    pub offset: Option<i64>,
+    /// `nextCursor` of the previous page, to continue after it.
+    pub cursor: Option<String>,
+}
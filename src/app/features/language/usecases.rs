@@ -0,0 +1,166 @@
+use crate::app::drivers::middlewares::auth::AuthenticatedUser;
+use crate::app::features::auth::permissions::{authorize, Action};
+use crate::error::AppError;
+use crate::utils::pagination::{PageLinks, PageRequest};
+use super::{
+    presenters::LanguagePresenter,
+    repositories::{CountryLanguageRepositoryInput, CreateLanguageRepositoryInput, FetchLanguagesRepositoryInput, LanguageRepository, UpdateLanguageRepositoryInput},
+};
+use std::sync::Arc;
+use actix_web::HttpResponse;
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct LanguageUsecase {
+    language_repo: Arc<dyn LanguageRepository>,
+    language_presenter: Arc<dyn LanguagePresenter>,
+}
+
+impl LanguageUsecase {
+    pub fn new(
+        language_repo: Arc<dyn LanguageRepository>,
+        language_presenter: Arc<dyn LanguagePresenter>,
+    ) -> Self {
+        Self {
+            language_repo,
+            language_presenter,
+        }
+    }
+
+    pub fn fetch_languages(&self, params: FetchLanguagesUsecaseInput) -> Result<HttpResponse, AppError> {
+        let languages = self.language_repo
+            .fetch_languages(
+                FetchLanguagesRepositoryInput {
+                    page: params.page,
+                }
+            )?;
+        let response = self.language_presenter.to_multi_json(languages, params.links);
+
+        Ok(response)
+    }
+
+    pub fn fetch_language(&self, id: Uuid) -> Result<HttpResponse, AppError> {
+        let language = self.language_repo.fetch_language(id)?;
+        let response = self.language_presenter.to_single_json(language);
+
+        Ok(response)
+    }
+
+    pub fn create_language(
+        &self,
+        actor: &AuthenticatedUser,
+        params: CreateLanguageUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageReferenceData)?;
+        let new_language = self.language_repo
+            .create_language(
+                CreateLanguageRepositoryInput {
+                    name: validate_language_name(params.name)?,
+                    symbol: params.symbol,
+                }
+            )?;
+        let response = self.language_presenter.to_single_json(new_language);
+
+        Ok(response)
+    }
+
+    pub fn update_language(
+        &self,
+        actor: &AuthenticatedUser,
+        id: Uuid,
+        params: UpdateLanguageUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageReferenceData)?;
+        if params.name.is_none() && params.symbol.is_none() {
+            return Err(AppError::UnprocessableEntity(json!({ "error": "No fields to update" })));
+        }
+
+        let updated_language = self.language_repo
+            .update_language(
+                id,
+                UpdateLanguageRepositoryInput {
+                    name: params.name.map(validate_language_name).transpose()?,
+                    symbol: params.symbol,
+                }
+            )?;
+        let response = self.language_presenter.to_single_json(updated_language);
+
+        Ok(response)
+    }
+
+    pub fn delete_language(&self, actor: &AuthenticatedUser, id: Uuid) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageReferenceData)?;
+        self.language_repo
+            .delete_language(id)?;
+        let response = self.language_presenter.to_http_res();
+
+        Ok(response)
+    }
+
+    pub fn attach_to_country(
+        &self,
+        actor: &AuthenticatedUser,
+        params: CountryLanguageUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageReferenceData)?;
+        self.language_repo
+            .attach_to_country(
+                CountryLanguageRepositoryInput {
+                    country_id: params.country_id,
+                    language_id: params.language_id,
+                }
+            )?;
+        let response = self.language_presenter.to_http_res();
+
+        Ok(response)
+    }
+
+    pub fn detach_from_country(
+        &self,
+        actor: &AuthenticatedUser,
+        params: CountryLanguageUsecaseInput,
+    ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::ManageReferenceData)?;
+        self.language_repo
+            .detach_from_country(
+                CountryLanguageRepositoryInput {
+                    country_id: params.country_id,
+                    language_id: params.language_id,
+                }
+            )?;
+        let response = self.language_presenter.to_http_res();
+
+        Ok(response)
+    }
+}
+
+fn validate_language_name(name: String) -> Result<String, AppError> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(AppError::UnprocessableEntity(json!({ "error": "Language name must not be empty" })));
+    }
+
+    Ok(name.to_string())
+}
+
+pub struct FetchLanguagesUsecaseInput {
+    pub page: PageRequest,
+    pub links: PageLinks,
+}
+
+pub struct CreateLanguageUsecaseInput {
+    pub name: String,
+    pub symbol: Option<String>,
+}
+
+pub struct UpdateLanguageUsecaseInput {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+}
+
+pub struct CountryLanguageUsecaseInput {
+    pub country_id: Uuid,
+    pub language_id: Uuid,
+}
@@ -2,3 +2,8 @@ pub mod healthcheck;
 pub mod organisation;
 pub mod country_connection;
 pub mod common;
+pub mod auth;
+pub mod chat;
+pub mod language;
+pub mod tile;
+pub mod search;
\ No newline at end of file
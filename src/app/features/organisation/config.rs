@@ -1,4 +1,4 @@
-use super::controllers::{create, list, delete, fetch, update};
+use super::controllers::{create, list, delete, fetch, nearby, update};
 use actix_web::{web, web::ServiceConfig};
 
 pub fn configure_services(cfg: &mut ServiceConfig) -> () {
@@ -8,6 +8,8 @@ pub fn configure_services(cfg: &mut ServiceConfig) -> () {
                 .to(create))
             .route("/list", web::get()
                 .to(list))
+            .route("/nearby", web::get()
+                .to(nearby))
             .route("/delete/{id}", web::delete()
                 .to(delete))
             .route("/update/{id}", web::put()
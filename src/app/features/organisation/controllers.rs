@@ -1,20 +1,30 @@
 use super::{
-    requests::{CreateOrganisationRequest, OrganisationsListQueryRequest, UpdateOrganisationRequest},
-    usecases::{CreateOrganisationUsecaseInput, FetchOrganisationsUsecaseInput, UpdateOrganisationUsecaseInput},
+    requests::{CreateOrganisationRequest, NearbyOrganisationsQueryRequest, OrganisationsListQueryRequest, UpdateOrganisationRequest},
+    usecases::{CreateOrganisationUsecaseInput, FetchNearbyOrganisationsUsecaseInput, FetchOrganisationsUsecaseInput, UpdateOrganisationUsecaseInput},
 };
-use crate::app::drivers::middlewares::state::AppState;
+use crate::constants::{geo, pagination::DEFAULT_LIMIT};
+use crate::app::drivers::middlewares::{auth::AuthenticatedUser, state::AppState};
 use crate::error::AppError;
+use crate::utils::geo::BoundingBox;
+use crate::utils::geojson::{FeatureCollection, ResponseFormat};
+use crate::utils::pagination::{PageLinks, PageRequest};
+use crate::utils::search::SearchQuery;
+use crate::utils::sorting::Sort;
 use actix_web::{web::{Data, Json, Path, Query}, HttpRequest, HttpResponse};
-use bigdecimal::BigDecimal;
 use uuid::Uuid;
-use std::cmp::min;
 
 #[utoipa::path(
     get,
     path = "/organisation/list",
     context_path = "/api",
     responses(
-        (status = 200, description = "Organisations list response", body = super::presenters::MultipleOrganisationsResponse),
+        (status = 200, description = "Organisations list response, or a GeoJSON FeatureCollection of points", content(
+            (super::presenters::MultipleOrganisationsResponse = "application/json"),
+            (FeatureCollection = "application/geo+json")
+        ), headers(
+            ("Link" = String, description = "RFC 8288 `first`, `prev`, `next` and `last` page links")
+        )),
+        (status = 422, description = "Malformed bbox, unknown format or sort field, q too long, invalid cursor or page size out of range", body = AppError),
         (status = 500, description = "Internal server error", body = AppError)
     ),
     params(OrganisationsListQueryRequest),
@@ -22,11 +32,9 @@ use std::cmp::min;
 )]
 pub async fn list(
     state: Data<AppState>,
+    req: HttpRequest,
     query: Query<OrganisationsListQueryRequest>
 ) -> Result<HttpResponse, AppError> {
-    let offset = min(query.offset.unwrap_or(0), 150);
-    let limit = query.limit.unwrap_or(20);
-
     state
         .di_container
         .organisation_usecase
@@ -38,8 +46,43 @@ pub async fn list(
                 address: query.address.clone(),
                 location_country_id: query.location_country_id,
                 organisation_type_id: query.organisation_type_id,
-                limit,
-                offset,
+                bounding_box: query.bbox.as_deref().map(BoundingBox::parse).transpose()?,
+                search: SearchQuery::parse(query.q.as_deref())?,
+                sort: Sort::parse(query.sort.as_deref())?,
+                page: PageRequest::parse(query.limit, query.offset, query.cursor.as_deref())?,
+                format: ResponseFormat::negotiate(query.format.as_deref(), &req)?,
+                links: PageLinks::from_request(&req),
+            }
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/organisation/nearby",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "Organisations within the radius, nearest first", body = super::presenters::NearbyOrganisationsResponse),
+        (status = 422, description = "Coordinates, radius or limit out of range", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    params(NearbyOrganisationsQueryRequest),
+    tag = "Organisation"
+)]
+pub async fn nearby(
+    state: Data<AppState>,
+    query: Query<NearbyOrganisationsQueryRequest>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .organisation_usecase
+        .fetch_nearby_organisations(
+            FetchNearbyOrganisationsUsecaseInput {
+                latitude: query.lat,
+                longitude: query.lon,
+                radius_km: query.radius_km.unwrap_or(geo::DEFAULT_NEARBY_RADIUS_KM),
+                location_country_id: query.location_country_id,
+                organisation_type_id: query.organisation_type_id,
+                limit: query.limit.unwrap_or(DEFAULT_LIMIT),
             }
         )
 }
@@ -79,14 +122,19 @@ pub async fn fetch(
     ),
     responses(
         (status = 200, description = "Organisation updated successfully", body = super::presenters::OrganisationContent),
+        (status = 422, description = "Coordinates out of range, given without each other or outside of the location country", body = AppError),
         (status = 400, description = "Bad request", body = AppError),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
         (status = 500, description = "Internal server error", body = AppError)
     ),
+    security(("bearer_auth" = [])),
     tag = "Organisation"
 )]
 pub async fn update(
     state: Data<AppState>,
     _req: HttpRequest,
+    user: AuthenticatedUser,
     form: Json<UpdateOrganisationRequest>,
     id: Path<Uuid>
 ) -> Result<HttpResponse, AppError> {
@@ -94,6 +142,7 @@ pub async fn update(
         .di_container
         .organisation_usecase
         .update_organisation(
+            &user,
             id.into_inner(),
             UpdateOrganisationUsecaseInput {
                 name: form.name.clone(),
@@ -103,10 +152,9 @@ pub async fn update(
                 description: form.description.clone(),
                 location_country_id: form.location_country_id,
                 organisation_type_id: form.organisation_type_id,
-                latitude: form.latitude.map(BigDecimal::try_from)
-                    .map(|v| v.expect("Invalid latitude value")),
-                longitude: form.longitude.map(BigDecimal::try_from)
-                    .map(|v| v.expect("Invalid longitude value")),
+                latitude: form.latitude,
+                longitude: form.longitude,
+                skip_location_check: form.skip_location_check,
             }
         )
 }
@@ -121,19 +169,23 @@ pub async fn update(
     responses(
         (status = 200, description = "Organisation deleted successfully"),
         (status = 400, description = "Bad request", body = AppError),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
         (status = 500, description = "Internal server error", body = AppError)
     ),
+    security(("bearer_auth" = [])),
     tag = "Organisation"
 )]
 pub async fn delete(
     state: Data<AppState>,
     _req: HttpRequest,
+    user: AuthenticatedUser,
     id: Path<Uuid>
 ) -> Result<HttpResponse, AppError> {
     state
         .di_container
         .organisation_usecase
-        .delete_organisation(id.into_inner())
+        .delete_organisation(&user, id.into_inner())
 }
 
 #[utoipa::path(
@@ -143,20 +195,26 @@ pub async fn delete(
     request_body = CreateOrganisationRequest,
     responses(
         (status = 200, description = "Organisation created successfully", body = super::presenters::OrganisationContent),
+        (status = 422, description = "Coordinates out of range, given without each other or outside of the location country", body = AppError),
         (status = 400, description = "Bad request", body = AppError),
+        (status = 401, description = "Missing or invalid access token", body = AppError),
+        (status = 403, description = "Role does not permit this action", body = AppError),
         (status = 500, description = "Internal server error", body = AppError)
     ),
+    security(("bearer_auth" = [])),
     tag = "Organisation"
 )]
 pub async fn create(
     state: Data<AppState>,
     _req: HttpRequest,
+    user: AuthenticatedUser,
     form: Json<CreateOrganisationRequest>
 ) -> Result<HttpResponse, AppError> {
     state
         .di_container
         .organisation_usecase
         .create_organisation(
+            &user,
             CreateOrganisationUsecaseInput{
                 name: form.name.clone(),
                 tel: form.tel.clone(),
@@ -165,10 +223,9 @@ pub async fn create(
                 description: form.description.clone(),
                 location_country_id: form.location_country_id,
                 organisation_type_id: form.organisation_type_id,
-                latitude: form.latitude.map(BigDecimal::try_from)
-                    .map(|v| v.expect("Invalid latitude value")),
-                longitude: form.longitude.map(BigDecimal::try_from)
-                    .map(|v| v.expect("Invalid longitude value")),
+                latitude: form.latitude,
+                longitude: form.longitude,
+                skip_location_check: form.skip_location_check,
             }
         )
 }
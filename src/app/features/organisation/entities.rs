@@ -4,7 +4,6 @@ use crate::data::{
 };
 use crate::error::*;
 use diesel::prelude::*;
-use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::NaiveDateTime;
@@ -24,8 +23,8 @@ pub struct Organisation {
     pub organisation_type_id: Option<Uuid>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
-    pub latitude: Option<BigDecimal>,
-    pub longitude: Option<BigDecimal>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
 }
 
 impl Organisation {
@@ -122,8 +121,8 @@ pub struct CreateOrganisation {
     pub description: Option<String>,
     pub location_country_id: Option<Uuid>,
     pub organisation_type_id: Option<Uuid>,
-    pub latitude: Option<BigDecimal>,
-    pub longitude: Option<BigDecimal>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
 }
 
 #[derive(AsChangeset)]
@@ -137,6 +136,6 @@ pub struct UpdateOrganisation {
     pub location_country_id: Option<Uuid>,
     pub organisation_type_id: Option<Uuid>,
     pub updated_at: NaiveDateTime,
-    pub latitude: Option<BigDecimal>,
-    pub longitude: Option<BigDecimal>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
 }
@@ -1,5 +1,9 @@
 use super::entities::Organisation;
+use crate::utils::geojson::{point, Feature, FeatureCollection};
+use crate::utils::pagination::{Page, PageLinks};
+use crate::utils::search::SearchMatch;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use actix_web::{
     HttpResponse,
     http::StatusCode
@@ -13,7 +17,9 @@ pub trait OrganisationPresenter: Send + Sync + 'static {
     // TODO: Tmp solution
     fn to_single_typed_json(&self, item: Organisation) -> HttpResponse<Organisation>;
     fn to_single_json(&self, item: Organisation) -> HttpResponse;
-    fn to_multi_json(&self, items: Vec<Organisation>) -> HttpResponse;
+    fn to_multi_json(&self, page: Page<(Organisation, Option<SearchMatch>)>, links: PageLinks) -> HttpResponse;
+    fn to_nearby_json(&self, items: Vec<(Organisation, f64)>, total: i64) -> HttpResponse;
+    fn to_multi_geo_json(&self, page: Page<(Organisation, Option<SearchMatch>)>, links: PageLinks) -> HttpResponse;
 }
 
 #[derive(Deserialize, Serialize, ToSchema)]
@@ -27,8 +33,45 @@ pub struct OrganisationContent {
     pub description: Option<String>,
     pub location_country_id: Option<Uuid>,
     pub organisation_type_id: Option<Uuid>,
+    /// WGS84 latitude in decimal degrees.
+    #[schema(example = 52.520008)]
+    pub latitude: Option<f64>,
+    /// WGS84 longitude in decimal degrees.
+    #[schema(example = 13.404954)]
+    pub longitude: Option<f64>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// Present only in results of a `q` search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<SearchMatchContent>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct SearchMatchContent {
+    /// Relevance to the query; higher is better.
+    pub rank: f32,
+    /// Name, address and description excerpt as HTML: the text is escaped
+    /// and matches are wrapped in `<mark>`.
+    #[schema(example = "<mark>German</mark> <mark>Embassy</mark> · 13-15 Avenue Franklin D. Roosevelt, <mark>Paris</mark>")]
+    pub snippet: String,
+}
+
+impl From<SearchMatch> for SearchMatchContent {
+    fn from(search_match: SearchMatch) -> Self {
+        Self {
+            rank: search_match.rank,
+            snippet: search_match.snippet,
+        }
+    }
+}
+
+impl From<(Organisation, Option<SearchMatch>)> for OrganisationContent {
+    fn from((org, search_match): (Organisation, Option<SearchMatch>)) -> Self {
+        Self {
+            search: search_match.map(SearchMatchContent::from),
+            ..Self::from(org)
+        }
+    }
 }
 
 impl From<Organisation> for OrganisationContent {
@@ -42,29 +85,73 @@ impl From<Organisation> for OrganisationContent {
             description: org.description,
             location_country_id: org.location_country_id,
             organisation_type_id: org.organisation_type_id,
+            latitude: org.latitude,
+            longitude: org.longitude,
             created_at: org.created_at,
             updated_at: org.updated_at,
+            search: None,
         }
     }
 }
 
 #[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct MultipleOrganisationsResponse {
     pub items: Vec<OrganisationContent>,
+    /// Number of organisations matching the filters across all pages.
     pub total: i64,
+    pub limit: i64,
+    /// `null` for pages requested with a cursor.
+    pub offset: Option<i64>,
+    pub has_more: bool,
+    /// Pass as `cursor` to fetch the next page; `null` on the last page.
+    pub next_cursor: Option<String>,
 }
 
-impl From<Vec<Organisation>> for MultipleOrganisationsResponse {
-    fn from(items: Vec<Organisation>) -> Self {
-        let response_items: Vec<OrganisationContent> = items
+impl From<Page<(Organisation, Option<SearchMatch>)>> for MultipleOrganisationsResponse {
+    fn from(page: Page<(Organisation, Option<SearchMatch>)>) -> Self {
+        let page = page.map(OrganisationContent::from);
+
+        Self {
+            items: page.items,
+            total: page.total,
+            limit: page.limit,
+            offset: page.offset,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NearbyOrganisationContent {
+    #[serde(flatten)]
+    pub organisation: OrganisationContent,
+    /// Great-circle distance from the requested point in kilometres.
+    pub distance_km: f64,
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct NearbyOrganisationsResponse {
+    pub items: Vec<NearbyOrganisationContent>,
+    /// Organisations within the radius, of which `items` holds the nearest.
+    pub total: i64,
+}
+
+impl From<(Vec<(Organisation, f64)>, i64)> for NearbyOrganisationsResponse {
+    fn from((items, total): (Vec<(Organisation, f64)>, i64)) -> Self {
+        let response_items: Vec<NearbyOrganisationContent> = items
             .into_iter()
-            .map(OrganisationContent::from)
+            .map(|(organisation, distance_km)| NearbyOrganisationContent {
+                organisation: OrganisationContent::from(organisation),
+                distance_km: (distance_km * 1000.0).round() / 1000.0,
+            })
             .collect();
-        let count = response_items.len() as i64;
 
         Self {
             items: response_items,
-            total: count,
+            total,
         }
     }
 }
@@ -94,9 +181,33 @@ impl OrganisationPresenter for OrganisationPresenterImpl {
         HttpResponse::Ok().json(response_content)
     }
 
-    fn to_multi_json(&self, items: Vec<Organisation>) -> HttpResponse {
-        let response_content = MultipleOrganisationsResponse::from(items);
+    fn to_multi_json(&self, page: Page<(Organisation, Option<SearchMatch>)>, links: PageLinks) -> HttpResponse {
+        let mut response = links.ok(&page);
+        let response_content = MultipleOrganisationsResponse::from(page);
+
+        response.json(response_content)
+    }
+
+    fn to_nearby_json(&self, items: Vec<(Organisation, f64)>, total: i64) -> HttpResponse {
+        let response_content = NearbyOrganisationsResponse::from((items, total));
 
         HttpResponse::Ok().json(response_content)
     }
+
+    fn to_multi_geo_json(&self, page: Page<(Organisation, Option<SearchMatch>)>, links: PageLinks) -> HttpResponse {
+        let response = links.ok(&page);
+        let features = page
+            .items
+            .into_iter()
+            .map(|item| {
+                let content = OrganisationContent::from(item);
+                let geometry = content.latitude.zip(content.longitude)
+                    .map(|(latitude, longitude)| point(latitude, longitude));
+
+                Feature::new(content.id, geometry, json!(content))
+            })
+            .collect();
+
+        FeatureCollection::new(features).respond(response)
+    }
 }
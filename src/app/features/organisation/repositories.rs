@@ -1,14 +1,30 @@
 use super::entities::{CreateOrganisation, Organisation, UpdateOrganisation};
+use crate::data::models::Country;
+use crate::data::schema::organisations;
 use crate::error::AppError;
 use crate::utils::db::DbPool;
+use diesel::dsl::sql;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::{Bool, Float4, Float8, Text};
+use crate::utils::geo::{BoundingBox, EARTH_RADIUS_KM};
+use crate::utils::pagination::{Cursor, Page, PageRequest, CURSOR_TIMESTAMP_FORMAT};
+use crate::utils::search::{html_escaped_sql, SearchMatch, SearchQuery, HEADLINE_OPTIONS, TEXT_SEARCH_CONFIG};
+use crate::utils::sorting::{Direction, Sort, SortField};
+use chrono::NaiveDateTime;
+use serde_json::json;
 use uuid::Uuid;
-use bigdecimal::BigDecimal;
 
 pub trait OrganisationRepository: Send + Sync + 'static {
     fn fetch_organisations(
         &self,
         params: FetchOrganisationsRepositoryInput
-    ) -> Result<Vec<Organisation>, AppError>;
+    ) -> Result<Page<(Organisation, Option<SearchMatch>)>, AppError>;
+
+    fn fetch_organisations_within(
+        &self,
+        params: FetchOrganisationsWithinRepositoryInput
+    ) -> Result<(Vec<Organisation>, i64), AppError>;
 
     fn create_organisation(
         &self,
@@ -30,6 +46,11 @@ pub trait OrganisationRepository: Send + Sync + 'static {
         id: Uuid,
         params: UpdateOrganisationRepositoryInput
     ) -> Result<Organisation, AppError>;
+
+    fn fetch_location_country(
+        &self,
+        id: Uuid
+    ) -> Result<Country, AppError>;
 }
 
 #[derive(Clone)]
@@ -43,49 +64,56 @@ impl OrganisationRepositoryImpl {
 }
 
 impl OrganisationRepository for OrganisationRepositoryImpl {
-    fn fetch_organisations(&self, params: FetchOrganisationsRepositoryInput) -> Result<Vec<Organisation>, AppError> {
-        use crate::data::schema::organisations;
-        use diesel::prelude::*;
-
+    fn fetch_organisations(&self, params: FetchOrganisationsRepositoryInput) -> Result<Page<(Organisation, Option<SearchMatch>)>, AppError> {
         let connection = &mut self.pool.get()?;
-        let query = {
-            let mut query = organisations::table.into_boxed();
-
-            if let Some(name) = &params.name {
-                query = query.filter(organisations::name.ilike(format!("%{}%", name)));
-            }
-
-            if let Some(tel) = &params.tel {
-                query = query.filter(organisations::tel.ilike(format!("%{}%", tel)));
-            }
-
-            if let Some(email) = &params.email {
-                query = query.filter(organisations::email.ilike(format!("%{}%", email)));
-            }
-
-            if let Some(address) = &params.address {
-                query = query.filter(organisations::address.ilike(format!("%{}%", address)));
-            }
-
-            if let Some(location_country_id) = params.location_country_id {
-                let ids = Organisation::fetch_ids_by_location_country(connection, location_country_id)?;
-                query = query.filter(organisations::id.eq_any(ids));
+        let total = filter_organisations(connection, &params)?
+            .count()
+            .get_result::<i64>(connection)?;
+        let mut query = order_organisations(filter_organisations(connection, &params)?, &params.sort, params.search.as_ref())
+            .limit(params.page.fetch_limit());
+        query = match &params.page {
+            PageRequest::Offset { offset, .. } => query.offset(*offset),
+            PageRequest::Cursor { after, .. } => {
+                let created_at = after.parse_key(|key| NaiveDateTime::parse_from_str(key, CURSOR_TIMESTAMP_FORMAT).ok())?;
+                query.filter(
+                    organisations::created_at.gt(created_at)
+                        .or(organisations::created_at.eq(created_at).and(organisations::id.gt(after.id))),
+                )
             }
+        };
+        let organisations: Vec<(Organisation, Option<SearchMatch>)> = match &params.search {
+            Some(search) => query
+                .select((Organisation::as_select(), search_rank(search), search_snippet(search)))
+                .load::<(Organisation, f32, String)>(connection)?
+                .into_iter()
+                .map(|(organisation, rank, snippet)| (organisation, Some(SearchMatch { rank, snippet })))
+                .collect(),
+            None => query
+                .load::<Organisation>(connection)?
+                .into_iter()
+                .map(|organisation| (organisation, None))
+                .collect(),
+        };
 
-            if let Some(organisation_type_id) = params.organisation_type_id {
-                let ids = Organisation::fetch_ids_by_organisation_type(connection, organisation_type_id)?;
-                query = query.filter(organisations::id.eq_any(ids));
-            }
+        let page = Page::new(organisations, total, &params.page, |(organisation, _)| {
+            Cursor::new(Some(organisation.created_at.format(CURSOR_TIMESTAMP_FORMAT).to_string()), organisation.id)
+        });
 
-            query
-        };
+        Ok(if params.sort.is_default() && params.search.is_none() { page } else { page.without_cursor() })
+    }
 
-        let organisations = query
+    fn fetch_organisations_within(&self, params: FetchOrganisationsWithinRepositoryInput) -> Result<(Vec<Organisation>, i64), AppError> {
+        let connection = &mut self.pool.get()?;
+        let total = filter_within_radius(&params)
+            .count()
+            .get_result::<i64>(connection)?;
+        let organisations = filter_within_radius(&params)
+            .order(distance_from(&params).asc())
+            .then_order_by(organisations::id)
             .limit(params.limit)
-            .offset(params.offset)
             .load::<Organisation>(connection)?;
 
-        Ok(organisations)
+        Ok((organisations, total))
     }
 
     fn create_organisation(&self, params: CreateOrganisationRepositoryInput) -> Result<Organisation, AppError> {
@@ -147,6 +175,17 @@ impl OrganisationRepository for OrganisationRepositoryImpl {
 
         Ok(updated_organisation)
     }
+
+    fn fetch_location_country(&self, id: Uuid) -> Result<Country, AppError> {
+        let connection = &mut self.pool.get()?;
+        let country = Country::get_by_id(connection, &id)
+            .map_err(|e| match e {
+                AppError::NotFound(_) => AppError::UnprocessableEntity(json!({ "error": "Location country does not exist" })),
+                e => e,
+            })?;
+
+        Ok(country)
+    }
 }
 
 pub struct UpdateOrganisationRepositoryInput {
@@ -157,8 +196,8 @@ pub struct UpdateOrganisationRepositoryInput {
     pub description: Option<String>,
     pub location_country_id: Option<Uuid>,
     pub organisation_type_id: Option<Uuid>,
-    pub latitude: Option<BigDecimal>,
-    pub longitude: Option<BigDecimal>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
 }
 
 pub struct FetchOrganisationsRepositoryInput {
@@ -168,8 +207,25 @@ pub struct FetchOrganisationsRepositoryInput {
     pub address: Option<String>,
     pub location_country_id: Option<Uuid>,
     pub organisation_type_id: Option<Uuid>,
-    pub limit: i64,
-    pub offset: i64,
+    pub bounding_box: Option<BoundingBox>,
+    pub search: Option<SearchQuery>,
+    pub sort: Sort<OrganisationSortField>,
+    pub page: PageRequest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrganisationSortField {
+    Name,
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl SortField for OrganisationSortField {
+    const ALLOWED: &'static [(&'static str, Self)] = &[
+        ("name", Self::Name),
+        ("created_at", Self::CreatedAt),
+        ("updated_at", Self::UpdatedAt),
+    ];
 }
 
 pub struct CreateOrganisationRepositoryInput {
@@ -180,6 +236,196 @@ pub struct CreateOrganisationRepositoryInput {
     pub description: Option<String>,
     pub location_country_id: Option<Uuid>,
     pub organisation_type_id: Option<Uuid>,
-    pub latitude: Option<BigDecimal>,
-    pub longitude: Option<BigDecimal>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+pub struct FetchOrganisationsWithinRepositoryInput {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_km: f64,
+    pub location_country_id: Option<Uuid>,
+    pub organisation_type_id: Option<Uuid>,
+    pub limit: i64,
+}
+
+/// Organisations within the radius matching the filters, shared by the
+/// query for the nearest ones and the count of all of them.
+fn filter_within_radius(params: &FetchOrganisationsWithinRepositoryInput) -> organisations::BoxedQuery<'static, Pg> {
+    // Same expression as organisations_location_earth_idx, so the cube
+    // around the circle is answered by the GiST index. The radius is
+    // passed as an angle because earthdistance uses its own earth radius.
+    let radius = params.radius_km / EARTH_RADIUS_KM;
+    let mut query = organisations::table
+        .filter(
+            sql::<Bool>("earth_box(ll_to_earth(")
+                .bind::<Float8, _>(params.latitude)
+                .sql(", ")
+                .bind::<Float8, _>(params.longitude)
+                .sql("), ")
+                .bind::<Float8, _>(radius)
+                .sql(" * earth()) @> organisation_location(organisations.latitude, organisations.longitude)"),
+        )
+        // The box also holds its corners, which lie outside the circle.
+        .filter(distance_from(params).le(sql::<Float8>("earth() * ").bind::<Float8, _>(radius)))
+        .into_boxed();
+
+    if let Some(location_country_id) = params.location_country_id {
+        query = query.filter(organisations::location_country_id.eq(location_country_id));
+    }
+
+    if let Some(organisation_type_id) = params.organisation_type_id {
+        query = query.filter(organisations::organisation_type_id.eq(organisation_type_id));
+    }
+
+    query
+}
+
+/// Distance of an organisation from the centre of the search, in the units
+/// of earthdistance's `earth()`.
+fn distance_from(params: &FetchOrganisationsWithinRepositoryInput) -> Box<dyn BoxableExpression<organisations::table, Pg, SqlType = Float8>> {
+    Box::new(
+        sql::<Float8>("earth_distance(ll_to_earth(")
+            .bind::<Float8, _>(params.latitude)
+            .sql(", ")
+            .bind::<Float8, _>(params.longitude)
+            .sql("), organisation_location(organisations.latitude, organisations.longitude))"),
+    )
+}
+
+/// Organisations matching the list filters, shared by the page query and
+/// the count of all matching rows.
+fn filter_organisations<'a>(
+    connection: &mut PgConnection,
+    params: &'a FetchOrganisationsRepositoryInput,
+) -> Result<organisations::BoxedQuery<'a, Pg>, AppError> {
+    let mut query = organisations::table.into_boxed();
+
+    if let Some(name) = &params.name {
+        query = query.filter(organisations::name.ilike(format!("%{}%", name)));
+    }
+
+    if let Some(tel) = &params.tel {
+        query = query.filter(organisations::tel.ilike(format!("%{}%", tel)));
+    }
+
+    if let Some(email) = &params.email {
+        query = query.filter(organisations::email.ilike(format!("%{}%", email)));
+    }
+
+    if let Some(address) = &params.address {
+        query = query.filter(organisations::address.ilike(format!("%{}%", address)));
+    }
+
+    if let Some(location_country_id) = params.location_country_id {
+        let ids = Organisation::fetch_ids_by_location_country(connection, location_country_id)?;
+        query = query.filter(organisations::id.eq_any(ids));
+    }
+
+    if let Some(organisation_type_id) = params.organisation_type_id {
+        let ids = Organisation::fetch_ids_by_organisation_type(connection, organisation_type_id)?;
+        query = query.filter(organisations::id.eq_any(ids));
+    }
+
+    if let Some(bounding_box) = &params.bounding_box {
+        query = filter_by_bounding_box(query, bounding_box);
+    }
+
+    if let Some(search) = &params.search {
+        query = query.filter(search_filter(search));
+    }
+
+    Ok(query)
+}
+
+/// Organisations containing every word of the query as a prefix, or whose
+/// name contains words similar enough to the query to be a misspelling.
+fn search_filter(search: &SearchQuery) -> Box<dyn BoxableExpression<organisations::table, Pg, SqlType = Bool>> {
+    match &search.all_words {
+        Some(all_words) => Box::new(
+            sql::<Bool>(&format!("(organisations.search_vector @@ to_tsquery('{}', ", TEXT_SEARCH_CONFIG))
+                .bind::<Text, _>(all_words.clone())
+                .sql(") OR ")
+                .bind::<Text, _>(search.text.clone())
+                .sql(" <% organisations.name)"),
+        ),
+        None => Box::new(sql::<Bool>("").bind::<Text, _>(search.text.clone()).sql(" <% organisations.name")),
+    }
+}
+
+fn search_rank(search: &SearchQuery) -> Box<dyn BoxableExpression<organisations::table, Pg, SqlType = Float4>> {
+    match &search.all_words {
+        Some(all_words) => Box::new(
+            sql::<Float4>(&format!("ts_rank(organisations.search_vector, to_tsquery('{}', ", TEXT_SEARCH_CONFIG))
+                .bind::<Text, _>(all_words.clone())
+                .sql(")) + word_similarity(")
+                .bind::<Text, _>(search.text.clone())
+                .sql(", organisations.name)"),
+        ),
+        None => Box::new(
+            sql::<Float4>("word_similarity(")
+                .bind::<Text, _>(search.text.clone())
+                .sql(", organisations.name)"),
+        ),
+    }
+}
+
+/// Name, address and description with any of the query words highlighted;
+/// just the name for organisations found by similarity alone. The text is
+/// HTML-escaped either way.
+fn search_snippet(search: &SearchQuery) -> Box<dyn BoxableExpression<organisations::table, Pg, SqlType = Text>> {
+    let document = html_escaped_sql("concat_ws(' · ', organisations.name, organisations.address, organisations.description)");
+    let name = html_escaped_sql("organisations.name");
+    match &search.any_word {
+        Some(any_word) => Box::new(
+            sql::<Text>(&format!("CASE WHEN organisations.search_vector @@ to_tsquery('{}', ", TEXT_SEARCH_CONFIG))
+                .bind::<Text, _>(any_word.clone())
+                .sql(&format!(") THEN ts_headline('{}', {}, to_tsquery('{}', ", TEXT_SEARCH_CONFIG, document, TEXT_SEARCH_CONFIG))
+                .bind::<Text, _>(any_word.clone())
+                .sql(&format!("), '{}') ELSE {} END", HEADLINE_OPTIONS, name)),
+        ),
+        None => Box::new(sql::<Text>(&name)),
+    }
+}
+
+/// Requested order, or the best search matches first, followed by the
+/// default `(created_at, id)` order, which breaks ties and keeps pages stable.
+fn order_organisations<'a>(
+    mut query: organisations::BoxedQuery<'a, Pg>,
+    sort: &Sort<OrganisationSortField>,
+    search: Option<&SearchQuery>,
+) -> organisations::BoxedQuery<'a, Pg> {
+    if let Some(search) = search.filter(|_| sort.is_default()) {
+        query = query.then_order_by(search_rank(search).desc());
+    }
+
+    for &(field, direction) in &sort.fields {
+        query = match (field, direction) {
+            (OrganisationSortField::Name, Direction::Asc) => query.then_order_by(organisations::name.asc()),
+            (OrganisationSortField::Name, Direction::Desc) => query.then_order_by(organisations::name.desc()),
+            (OrganisationSortField::CreatedAt, Direction::Asc) => query.then_order_by(organisations::created_at.asc()),
+            (OrganisationSortField::CreatedAt, Direction::Desc) => query.then_order_by(organisations::created_at.desc()),
+            (OrganisationSortField::UpdatedAt, Direction::Asc) => query.then_order_by(organisations::updated_at.asc()),
+            (OrganisationSortField::UpdatedAt, Direction::Desc) => query.then_order_by(organisations::updated_at.desc()),
+        };
+    }
+
+    query
+        .then_order_by(organisations::created_at.asc())
+        .then_order_by(organisations::id.asc())
+}
+
+/// Restricts the query to organisations located inside the box; boxes that
+/// cross the antimeridian match either side of it.
+pub(crate) fn filter_by_bounding_box<'a>(
+    query: organisations::BoxedQuery<'a, Pg>,
+    bounding_box: &BoundingBox,
+) -> organisations::BoxedQuery<'a, Pg> {
+    let query = query.filter(organisations::latitude.between(bounding_box.min_lat, bounding_box.max_lat));
+
+    if bounding_box.crosses_antimeridian() {
+        query.filter(organisations::longitude.ge(bounding_box.min_lon).or(organisations::longitude.le(bounding_box.max_lon)))
+    } else {
+        query.filter(organisations::longitude.between(bounding_box.min_lon, bounding_box.max_lon))
+    }
 }
@@ -13,6 +13,9 @@ pub struct CreateOrganisationRequest {
     pub organisation_type_id: Option<Uuid>,
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
+    /// Store the coordinates even if they fall outside the location country.
+    #[serde(default)]
+    pub skip_location_check: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -26,16 +29,53 @@ pub struct UpdateOrganisationRequest {
     pub organisation_type_id: Option<Uuid>,
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
+    /// Store the coordinates even if they fall outside the location country.
+    #[serde(default)]
+    pub skip_location_check: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug, ToSchema, IntoParams)]
 pub struct OrganisationsListQueryRequest {
+    /// Full-text search over name, address and description, tolerant of
+    /// misspelled names. Results are ranked by relevance unless `sort` is
+    /// given, and carry a highlighted snippet.
+    #[param(example = "german embassy paris")]
+    pub q: Option<String>,
     pub name: Option<String>,
     pub tel: Option<String>,
     pub email: Option<String>,
     pub address: Option<String>,
     pub location_country_id: Option<Uuid>,
     pub organisation_type_id: Option<Uuid>,
+    /// Viewport as `minLon,minLat,maxLon,maxLat`; `minLon` greater than
+    /// `maxLon` selects a box crossing the antimeridian.
+    #[param(example = "5.8,47.2,15.1,55.1")]
+    pub bbox: Option<String>,
+    /// Page size, default 20, at most 100.
     pub limit: Option<i64>,
+    /// Rows to skip; cannot be combined with `cursor`.
     pub offset: Option<i64>,
+    /// `nextCursor` of the previous page, to continue after it. Only
+    /// available in the default order and without `q`.
+    pub cursor: Option<String>,
+    /// Comma separated `name`, `created_at` or `updated_at`, each optionally
+    /// prefixed with `-` for descending order; default `created_at`.
+    #[param(example = "name,-created_at")]
+    pub sort: Option<String>,
+    /// `json` (default) or `geojson`; `Accept: application/geo+json` also selects GeoJSON.
+    pub format: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema, IntoParams)]
+pub struct NearbyOrganisationsQueryRequest {
+    /// Latitude of the search centre in decimal degrees.
+    pub lat: f64,
+    /// Longitude of the search centre in decimal degrees.
+    pub lon: f64,
+    /// Search radius in kilometres, default 50, at most 2000.
+    pub radius_km: Option<f64>,
+    pub location_country_id: Option<Uuid>,
+    pub organisation_type_id: Option<Uuid>,
+    /// Number of nearest organisations to return, default 20, at most 100.
+    pub limit: Option<i64>,
 }
@@ -1,11 +1,25 @@
+use crate::constants::{geo, pagination::MAX_LIMIT};
 use crate::error::AppError;
+use crate::utils::geojson::ResponseFormat;
+use crate::utils::pagination::{PageLinks, PageRequest};
+use crate::utils::search::SearchQuery;
+use crate::utils::sorting::Sort;
+use crate::utils::geo::{
+    distance_to_boundary_km, haversine_km, is_valid_latitude, is_valid_longitude, polygons_contain, polygons_from_geo_json,
+    BoundingBox,
+};
 use super::{
     presenters::OrganisationPresenter,
-    repositories::{CreateOrganisationRepositoryInput, FetchOrganisationsRepositoryInput, OrganisationRepository, UpdateOrganisationRepositoryInput},
+    repositories::{
+        CreateOrganisationRepositoryInput, FetchOrganisationsRepositoryInput, FetchOrganisationsWithinRepositoryInput,
+        OrganisationRepository, OrganisationSortField, UpdateOrganisationRepositoryInput,
+    },
 };
+use crate::app::drivers::middlewares::auth::AuthenticatedUser;
+use crate::app::features::auth::permissions::{authorize, Action};
 use std::sync::Arc;
 use actix_web::HttpResponse;
-use bigdecimal::BigDecimal;
+use serde_json::json;
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -27,8 +41,16 @@ impl OrganisationUsecase {
 
     pub fn create_organisation(
         &self,
+        actor: &AuthenticatedUser,
         params: CreateOrganisationUsecaseInput,
     ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::CreateOrganisation)?;
+        check_coordinates(params.latitude, params.longitude)?;
+        check_coordinate_pair(params.latitude, params.longitude)?;
+        if !params.skip_location_check {
+            self.check_location(params.location_country_id, params.latitude, params.longitude)?;
+        }
+
         let new_organisation = self.organisation_repo
             .create_organisation(
                 CreateOrganisationRepositoryInput {
@@ -50,9 +72,30 @@ impl OrganisationUsecase {
 
     pub fn update_organisation(
         &self,
+        actor: &AuthenticatedUser,
         id: Uuid,
         params: UpdateOrganisationUsecaseInput,
     ) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::UpdateOrganisation)?;
+        check_coordinates(params.latitude, params.longitude)?;
+        let coordinates_changed = params.latitude.is_some() || params.longitude.is_some();
+        let location_changed = params.location_country_id.is_some() || coordinates_changed;
+        if location_changed {
+            let current = self.organisation_repo.fetch_organisation(id)?;
+            let latitude = params.latitude.or(current.latitude);
+            let longitude = params.longitude.or(current.longitude);
+            if coordinates_changed {
+                check_coordinate_pair(latitude, longitude)?;
+            }
+            if !params.skip_location_check {
+                self.check_location(
+                    params.location_country_id.or(current.location_country_id),
+                    latitude,
+                    longitude,
+                )?;
+            }
+        }
+
         let updated_organisation = self.organisation_repo
             .update_organisation(
                 id,
@@ -77,6 +120,12 @@ impl OrganisationUsecase {
         &self,
         params: FetchOrganisationsUsecaseInput,
     ) -> Result<HttpResponse, AppError> {
+        params.sort.check_page(&params.page)?;
+        if params.search.is_some() && params.page.cursor().is_some() {
+            return Err(AppError::UnprocessableEntity(json!({
+                "error": "cursor cannot be combined with q; use offset paging for search results"
+            })));
+        }
         let organisations = self.organisation_repo
             .fetch_organisations(
                 FetchOrganisationsRepositoryInput {
@@ -86,16 +135,22 @@ impl OrganisationUsecase {
                     location_country_id: params.location_country_id,
                     organisation_type_id: params.organisation_type_id,
                     address: params.address,
-                    limit: params.limit,
-                    offset: params.offset,
+                    bounding_box: params.bounding_box,
+                    search: params.search,
+                    sort: params.sort,
+                    page: params.page,
                 }
             )?;
-        let response = self.organisation_presenter.to_multi_json(organisations);
+        let response = match params.format {
+            ResponseFormat::Json => self.organisation_presenter.to_multi_json(organisations, params.links),
+            ResponseFormat::GeoJson => self.organisation_presenter.to_multi_geo_json(organisations, params.links),
+        };
 
         Ok(response)
     }
 
-    pub fn delete_organisation(&self, id: Uuid) -> Result<HttpResponse, AppError> {
+    pub fn delete_organisation(&self, actor: &AuthenticatedUser, id: Uuid) -> Result<HttpResponse, AppError> {
+        authorize(actor, Action::DeleteOrganisation)?;
         self.organisation_repo
             .delete_organisation(id)?;
         let response = self.organisation_presenter.to_http_res();
@@ -103,12 +158,134 @@ impl OrganisationUsecase {
         Ok(response)
     }
 
+    /// Organisations within `radius_km` of a point, nearest first. The
+    /// nearest `limit` candidates are found with the spatial index in SQL;
+    /// exact great-circle distances are computed here.
+    pub fn fetch_nearby_organisations(&self, params: FetchNearbyOrganisationsUsecaseInput) -> Result<HttpResponse, AppError> {
+        if !is_valid_latitude(params.latitude) || !is_valid_longitude(params.longitude) {
+            return Err(AppError::UnprocessableEntity(json!({
+                "error": "lat must be within [-90, 90] and lon within [-180, 180]"
+            })));
+        }
+        if !(params.radius_km > 0.0 && params.radius_km <= geo::MAX_NEARBY_RADIUS_KM) {
+            return Err(AppError::UnprocessableEntity(json!({
+                "error": format!("radius_km must be greater than 0 and at most {}", geo::MAX_NEARBY_RADIUS_KM)
+            })));
+        }
+
+        if !(1..=MAX_LIMIT).contains(&params.limit) {
+            return Err(AppError::UnprocessableEntity(json!({
+                "error": format!("limit must be between 1 and {}", MAX_LIMIT)
+            })));
+        }
+
+        let (candidates, total) = self.organisation_repo
+            .fetch_organisations_within(
+                FetchOrganisationsWithinRepositoryInput {
+                    latitude: params.latitude,
+                    longitude: params.longitude,
+                    radius_km: params.radius_km,
+                    location_country_id: params.location_country_id,
+                    organisation_type_id: params.organisation_type_id,
+                    limit: params.limit,
+                }
+            )?;
+
+        let nearby: Vec<_> = candidates
+            .into_iter()
+            .filter_map(|organisation| {
+                let (lat, lon) = (organisation.latitude?, organisation.longitude?);
+                let distance_km = haversine_km(params.latitude, params.longitude, lat, lon);
+
+                (distance_km <= params.radius_km).then_some((organisation, distance_km))
+            })
+            .collect();
+
+        let response = self.organisation_presenter.to_nearby_json(nearby, total);
+
+        Ok(response)
+    }
+
     pub fn fetch_organisation(&self, id: Uuid) -> Result<HttpResponse, AppError> {
         let organisation = self.organisation_repo.fetch_organisation(id)?;
         let response = self.organisation_presenter.to_single_json(organisation);
 
         Ok(response)
     }
+
+    /// Rejects coordinates outside the borders of the location country,
+    /// allowing `LOCATION_TOLERANCE_KM` for simplified geometries. Nothing is
+    /// checked while the country, its geometry or either coordinate is missing.
+    fn check_location(
+        &self,
+        location_country_id: Option<Uuid>,
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+    ) -> Result<(), AppError> {
+        let (Some(country_id), Some(lat), Some(lon)) = (location_country_id, latitude, longitude) else {
+            return Ok(());
+        };
+        let country = self.organisation_repo.fetch_location_country(country_id)?;
+        let Some(polygons) = country.geo_json.as_ref().and_then(polygons_from_geo_json) else {
+            return Ok(());
+        };
+
+        if polygons_contain(&polygons, lat, lon) {
+            return Ok(());
+        }
+        let distance_km = distance_to_boundary_km(&polygons, lat, lon);
+        if distance_km <= geo::LOCATION_TOLERANCE_KM {
+            return Ok(());
+        }
+
+        let swapped = is_valid_latitude(lon) && polygons_contain(&polygons, lon, lat);
+        let hint = if swapped {
+            "Latitude and longitude appear to be swapped"
+        } else {
+            "Check the coordinates and the location country, or set skip_location_check for a legitimate exception"
+        };
+
+        Err(AppError::UnprocessableEntity(json!({
+            "error": format!("Coordinates ({}, {}) are outside of {}", lat, lon, country.name),
+            "details": {
+                "locationCountryId": country.id,
+                "locationCountryName": country.name,
+                "latitude": lat,
+                "longitude": lon,
+                "distanceKm": distance_km.round(),
+                "swappedCoordinates": swapped,
+            },
+            "hint": hint,
+        })))
+    }
+}
+
+/// Rejects coordinates outside of the WGS84 ranges, which the database
+/// would refuse as well.
+fn check_coordinates(latitude: Option<f64>, longitude: Option<f64>) -> Result<(), AppError> {
+    let latitude_valid = latitude.is_none_or(is_valid_latitude);
+    let longitude_valid = longitude.is_none_or(is_valid_longitude);
+    if latitude_valid && longitude_valid {
+        return Ok(());
+    }
+
+    Err(AppError::UnprocessableEntity(json!({
+        "error": "latitude must be within [-90, 90] and longitude within [-180, 180]",
+        "details": { "latitude": latitude, "longitude": longitude },
+    })))
+}
+
+/// Rejects a latitude without a longitude or the other way round, since
+/// neither locates the organisation on its own.
+fn check_coordinate_pair(latitude: Option<f64>, longitude: Option<f64>) -> Result<(), AppError> {
+    if latitude.is_some() == longitude.is_some() {
+        return Ok(());
+    }
+
+    Err(AppError::UnprocessableEntity(json!({
+        "error": "latitude and longitude must be given together",
+        "details": { "latitude": latitude, "longitude": longitude },
+    })))
 }
 
 pub struct UpdateOrganisationUsecaseInput {
@@ -119,8 +296,9 @@ pub struct UpdateOrganisationUsecaseInput {
     pub description: Option<String>,
     pub location_country_id: Option<Uuid>,
     pub organisation_type_id: Option<Uuid>,
-    pub latitude: Option<BigDecimal>,
-    pub longitude: Option<BigDecimal>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub skip_location_check: bool,
 }
 
 pub struct CreateOrganisationUsecaseInput {
@@ -131,8 +309,9 @@ pub struct CreateOrganisationUsecaseInput {
     pub description: Option<String>,
     pub location_country_id: Option<Uuid>,
     pub organisation_type_id: Option<Uuid>,
-    pub latitude: Option<BigDecimal>,
-    pub longitude: Option<BigDecimal>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub skip_location_check: bool,
 }
 
 pub struct FetchOrganisationsUsecaseInput {
@@ -142,6 +321,19 @@ pub struct FetchOrganisationsUsecaseInput {
     pub address: Option<String>,
     pub location_country_id: Option<Uuid>,
     pub organisation_type_id: Option<Uuid>,
+    pub bounding_box: Option<BoundingBox>,
+    pub search: Option<SearchQuery>,
+    pub sort: Sort<OrganisationSortField>,
+    pub page: PageRequest,
+    pub format: ResponseFormat,
+    pub links: PageLinks,
+}
+
+pub struct FetchNearbyOrganisationsUsecaseInput {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_km: f64,
+    pub location_country_id: Option<Uuid>,
+    pub organisation_type_id: Option<Uuid>,
     pub limit: i64,
-    pub offset: i64,
 }
@@ -0,0 +1,10 @@
+use super::controllers::search;
+use actix_web::{web, web::ServiceConfig};
+
+pub fn configure_services(cfg: &mut ServiceConfig) {
+    cfg.service(
+        web::scope("/search")
+            .route("", web::get()
+                .to(search))
+    );
+}
@@ -0,0 +1,38 @@
+use super::{
+    entities::SearchKind,
+    requests::SearchQueryParams,
+    usecases::SearchUsecaseInput,
+};
+use crate::app::drivers::middlewares::state::AppState;
+use crate::constants::pagination::DEFAULT_LIMIT;
+use crate::error::AppError;
+use crate::utils::search::SearchQuery;
+use actix_web::{web::{Data, Query}, HttpResponse};
+
+#[utoipa::path(
+    get,
+    path = "/search",
+    context_path = "/api",
+    params(SearchQueryParams),
+    responses(
+        (status = 200, description = "Ranked hits across countries, organisations, country connections and chats", body = super::presenters::SearchResponse),
+        (status = 422, description = "Missing or too long q, unknown kind or limit out of range", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Search"
+)]
+pub async fn search(
+    state: Data<AppState>,
+    query: Query<SearchQueryParams>
+) -> Result<HttpResponse, AppError> {
+    state
+        .di_container
+        .search_usecase
+        .search(
+            SearchUsecaseInput {
+                search: SearchQuery::parse(query.q.as_deref())?,
+                kinds: SearchKind::parse_list(query.kind.as_deref())?,
+                limit: query.limit.unwrap_or(DEFAULT_LIMIT),
+            }
+        )
+}
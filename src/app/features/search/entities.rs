@@ -0,0 +1,64 @@
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Kind of record a search hit points to, named as in the `kind` parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    Country,
+    Organisation,
+    CountryConnection,
+    Chat,
+}
+
+impl SearchKind {
+    pub const ALL: [SearchKind; 4] = [
+        SearchKind::Country,
+        SearchKind::Organisation,
+        SearchKind::CountryConnection,
+        SearchKind::Chat,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchKind::Country => "country",
+            SearchKind::Organisation => "organisation",
+            SearchKind::CountryConnection => "country_connection",
+            SearchKind::Chat => "chat",
+        }
+    }
+
+    /// Kinds selected by a comma separated `kind` parameter such as
+    /// `country,chat`; empty selects every kind.
+    pub fn parse_list(value: Option<&str>) -> Result<Vec<Self>, AppError> {
+        let mut kinds: Vec<Self> = Vec::new();
+        for name in value.unwrap_or_default().split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            let Some(kind) = Self::ALL.into_iter().find(|kind| kind.as_str() == name) else {
+                return Err(AppError::UnprocessableEntity(json!({
+                    "error": format!("Unknown kind '{}'", name),
+                    "allowed": Self::ALL.iter().map(Self::as_str).collect::<Vec<_>>()
+                })));
+            };
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+
+        Ok(if kinds.is_empty() { Self::ALL.to_vec() } else { kinds })
+    }
+}
+
+/// A record matching a global search, with a label to show it by.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub kind: SearchKind,
+    pub id: Uuid,
+    pub label: String,
+    /// Second line for the label, such as a country's capital or an
+    /// organisation's address.
+    pub detail: Option<String>,
+    pub rank: f32,
+}
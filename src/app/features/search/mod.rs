@@ -0,0 +1,7 @@
+pub mod entities;
+pub mod presenters;
+pub mod controllers;
+pub mod repositories;
+pub mod requests;
+pub mod usecases;
+pub mod config;
@@ -0,0 +1,58 @@
+use super::entities::{SearchHit, SearchKind};
+use serde::{Deserialize, Serialize};
+use actix_web::HttpResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub trait SearchPresenter: Send + Sync + 'static {
+    fn to_multi_json(&self, items: Vec<SearchHit>) -> HttpResponse;
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHitContent {
+    pub kind: SearchKind,
+    pub id: Uuid,
+    pub label: String,
+    pub detail: Option<String>,
+    pub rank: f32,
+}
+
+impl From<SearchHit> for SearchHitContent {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            kind: hit.kind,
+            id: hit.id,
+            label: hit.label,
+            detail: hit.detail,
+            rank: hit.rank,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResponse {
+    /// Hits of all kinds, best match first.
+    pub items: Vec<SearchHitContent>,
+}
+
+#[derive(Clone)]
+pub struct SearchPresenterImpl {}
+impl SearchPresenterImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+impl SearchPresenter for SearchPresenterImpl {
+    fn to_multi_json(&self, items: Vec<SearchHit>) -> HttpResponse {
+        let response_content = SearchResponse {
+            items: items
+                .into_iter()
+                .map(SearchHitContent::from)
+                .collect(),
+        };
+
+        HttpResponse::Ok().json(response_content)
+    }
+}
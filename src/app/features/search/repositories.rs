@@ -0,0 +1,129 @@
+use super::entities::{SearchHit, SearchKind};
+use crate::error::AppError;
+use crate::utils::db::DbPool;
+use crate::utils::search::{SearchQuery, TEXT_SEARCH_CONFIG};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Float4, Nullable, Text};
+use uuid::Uuid;
+
+pub trait SearchRepository: Send + Sync + 'static {
+    /// Best matches of one kind, highest rank first.
+    fn search(
+        &self,
+        kind: SearchKind,
+        params: &SearchRepositoryInput
+    ) -> Result<Vec<SearchHit>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct SearchRepositoryImpl {
+    pool: DbPool
+}
+impl SearchRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SearchRepository for SearchRepositoryImpl {
+    fn search(&self, kind: SearchKind, params: &SearchRepositoryInput) -> Result<Vec<SearchHit>, AppError> {
+        let connection = &mut self.pool.get()?;
+        let rows = diesel::sql_query(hits_query(kind))
+            .bind::<Nullable<Text>, _>(params.search.any_word.clone())
+            .bind::<Text, _>(params.search.text.clone())
+            .bind::<BigInt, _>(params.limit)
+            .load::<SearchHitRow>(connection)?;
+
+        let hits = rows
+            .into_iter()
+            .map(|row| SearchHit {
+                kind,
+                id: row.id,
+                label: row.label,
+                detail: row.detail,
+                rank: row.rank,
+            })
+            .collect();
+
+        Ok(hits)
+    }
+}
+
+pub struct SearchRepositoryInput {
+    pub search: SearchQuery,
+    pub limit: i64,
+}
+
+#[derive(QueryableByName)]
+struct SearchHitRow {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    id: Uuid,
+    #[diesel(sql_type = Text)]
+    label: String,
+    #[diesel(sql_type = Nullable<Text>)]
+    detail: Option<String>,
+    #[diesel(sql_type = Float4)]
+    rank: f32,
+}
+
+/// Query for the hits of one kind, bound to the `to_tsquery` input matching
+/// any query word (`$1`), the query text (`$2`) and the limit (`$3`).
+///
+/// Every kind yields a stored `document` to match the words against and a
+/// `name` matched by trigram word similarity, as in the organisation list;
+/// both are indexed. Matching any word rather than all of them lets a query
+/// like "german embassy in paris" also find the country Germany; the rank
+/// puts rows matching more of the words first, then rows whose name holds
+/// the query most closely.
+fn hits_query(kind: SearchKind) -> String {
+    let source = match kind {
+        SearchKind::Country => "
+            SELECT countries.id,
+                countries.name AS label,
+                countries.capital_city AS detail,
+                countries.search_vector AS document,
+                countries.name AS name
+            FROM countries",
+        SearchKind::Organisation => "
+            SELECT organisations.id,
+                organisations.name AS label,
+                coalesce(organisations.address, countries.name) AS detail,
+                organisations.search_vector AS document,
+                organisations.name AS name
+            FROM organisations
+            LEFT JOIN countries ON countries.id = organisations.location_country_id",
+        // Labelled by the embassy, or the consulate, and the country it is
+        // located in, e.g. "Embassy of Germany · France".
+        SearchKind::CountryConnection => "
+            SELECT countries_connections.id,
+                coalesce(nullif(concat_ws(' · ', coalesce(embassy.name, consulate.name), countries.name), ''), 'Country connection') AS label,
+                left(countries_connections.common_info, 140) AS detail,
+                countries_connections.search_vector AS document,
+                countries_connections.search_name AS name
+            FROM countries_connections
+            LEFT JOIN organisations embassy ON embassy.id = countries_connections.embassy_org_id
+            LEFT JOIN organisations consulate ON consulate.id = countries_connections.consulate_org_id
+            LEFT JOIN countries ON countries.id = countries_connections.location_country_id",
+        // Labelled by the app and the location country of the connection the
+        // chat belongs to, e.g. "Telegram · France".
+        SearchKind::Chat => "
+            SELECT chats.id,
+                concat_ws(' · ', coalesce(chats.app, 'Chat'), countries.name) AS label,
+                left(coalesce(chats.info, chats.link), 140) AS detail,
+                chats.search_vector AS document,
+                coalesce(chats.app, '') AS name
+            FROM chats
+            LEFT JOIN countries_connections ON countries_connections.id = chats.origin_country_connection_id
+            LEFT JOIN countries ON countries.id = countries_connections.location_country_id",
+    };
+
+    format!(
+        "SELECT id, label, detail,
+            coalesce(ts_rank(document, to_tsquery('{config}', $1)), 0) + word_similarity($2, name) AS rank
+        FROM ({source}) hits
+        WHERE document @@ to_tsquery('{config}', $1) OR $2 <% name
+        ORDER BY rank DESC, label, id
+        LIMIT $3",
+        config = TEXT_SEARCH_CONFIG,
+    )
+}
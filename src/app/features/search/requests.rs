@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, Serialize, Debug, ToSchema, IntoParams)]
+pub struct SearchQueryParams {
+    /// Words to look for in countries, organisations, country connections
+    /// and chats; each word also matches as a prefix, and misspelled names
+    /// are found by similarity.
+    #[param(example = "german embassy in paris")]
+    pub q: Option<String>,
+    /// Comma separated `country`, `organisation`, `country_connection` or
+    /// `chat` to search only those kinds; default all of them.
+    #[param(example = "country,organisation")]
+    pub kind: Option<String>,
+    /// Number of hits, default 20, at most 100.
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,62 @@
+use crate::constants::pagination::MAX_LIMIT;
+use crate::error::AppError;
+use crate::utils::search::SearchQuery;
+use super::{
+    entities::SearchKind,
+    presenters::SearchPresenter,
+    repositories::{SearchRepository, SearchRepositoryInput},
+};
+use std::sync::Arc;
+use actix_web::HttpResponse;
+use serde_json::json;
+
+#[derive(Clone)]
+pub struct SearchUsecase {
+    search_repo: Arc<dyn SearchRepository>,
+    search_presenter: Arc<dyn SearchPresenter>,
+}
+
+impl SearchUsecase {
+    pub fn new(
+        search_repo: Arc<dyn SearchRepository>,
+        search_presenter: Arc<dyn SearchPresenter>,
+    ) -> Self {
+        Self {
+            search_repo,
+            search_presenter,
+        }
+    }
+
+    /// Searches every requested kind and merges the hits by rank; ties keep
+    /// the order of the kinds.
+    pub fn search(&self, params: SearchUsecaseInput) -> Result<HttpResponse, AppError> {
+        let Some(search) = params.search else {
+            return Err(AppError::UnprocessableEntity(json!({ "error": "q is required" })));
+        };
+        if !(1..=MAX_LIMIT).contains(&params.limit) {
+            return Err(AppError::UnprocessableEntity(json!({
+                "error": format!("limit must be between 1 and {}", MAX_LIMIT)
+            })));
+        }
+
+        let input = SearchRepositoryInput {
+            search,
+            limit: params.limit,
+        };
+        let mut hits = Vec::new();
+        for kind in params.kinds {
+            hits.extend(self.search_repo.search(kind, &input)?);
+        }
+        hits.sort_by(|a, b| b.rank.total_cmp(&a.rank));
+        hits.truncate(params.limit as usize);
+        let response = self.search_presenter.to_multi_json(hits);
+
+        Ok(response)
+    }
+}
+
+pub struct SearchUsecaseInput {
+    pub search: Option<SearchQuery>,
+    pub kinds: Vec<SearchKind>,
+    pub limit: i64,
+}
@@ -0,0 +1,10 @@
+use super::controllers::fetch_tile;
+use actix_web::{web, web::ServiceConfig};
+
+pub fn configure_services(cfg: &mut ServiceConfig) {
+    cfg.service(
+        web::scope("/tiles")
+            .route("/{z}/{x}/{y}.mvt", web::get()
+                .to(fetch_tile))
+    );
+}
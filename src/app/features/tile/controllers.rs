@@ -0,0 +1,39 @@
+use super::usecases::FetchTileUsecaseInput;
+use crate::app::drivers::middlewares::state::AppState;
+use crate::error::AppError;
+use actix_web::{web::{Data, Path}, HttpResponse};
+
+#[utoipa::path(
+    get,
+    path = "/tiles/{z}/{x}/{y}.mvt",
+    context_path = "/api",
+    params(
+        ("z" = u32, Path, description = "Zoom level, 0 to 22"),
+        ("x" = u32, Path, description = "Tile column, 0 to 2^z - 1"),
+        ("y" = u32, Path, description = "Tile row from the top, 0 to 2^z - 1")
+    ),
+    responses(
+        (status = 200, description = "Mapbox Vector Tile with a `countries` polygon layer and an `organisations` point layer; empty layers are left out",
+            content_type = "application/vnd.mapbox-vector-tile", body = Vec<u8>),
+        (status = 422, description = "Zoom level or tile coordinates out of range", body = AppError),
+        (status = 500, description = "Internal server error", body = AppError)
+    ),
+    tag = "Tiles"
+)]
+pub async fn fetch_tile(
+    state: Data<AppState>,
+    path: Path<(u32, u32, u32)>,
+) -> Result<HttpResponse, AppError> {
+    let (z, x, y) = path.into_inner();
+
+    state
+        .di_container
+        .tile_usecase
+        .fetch_tile(
+            FetchTileUsecaseInput {
+                z,
+                x,
+                y,
+            }
+        )
+}
@@ -0,0 +1,5 @@
+pub mod presenters;
+pub mod controllers;
+pub mod repositories;
+pub mod usecases;
+pub mod config;
@@ -0,0 +1,23 @@
+use crate::constants::tiles::CACHE_MAX_AGE_SECONDS;
+use crate::utils::mvt::MVT_CONTENT_TYPE;
+use actix_web::{http::header::CACHE_CONTROL, HttpResponse};
+
+pub trait TilePresenter: Send + Sync + 'static {
+    fn to_mvt(&self, tile: Vec<u8>) -> HttpResponse;
+}
+
+#[derive(Clone)]
+pub struct TilePresenterImpl {}
+impl TilePresenterImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+impl TilePresenter for TilePresenterImpl {
+    fn to_mvt(&self, tile: Vec<u8>) -> HttpResponse {
+        HttpResponse::Ok()
+            .content_type(MVT_CONTENT_TYPE)
+            .insert_header((CACHE_CONTROL, format!("public, max-age={}", CACHE_MAX_AGE_SECONDS)))
+            .body(tile)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::app::features::organisation::entities::Organisation;
+use crate::app::features::organisation::repositories::filter_by_bounding_box;
+use crate::data::models::Country;
+use crate::data::schema::organisations;
+use crate::error::AppError;
+use crate::utils::db::DbPool;
+use crate::utils::geo::{BoundingBox, GeometryDetail};
+use diesel::prelude::*;
+use std::collections::HashMap;
+
+pub trait TileRepository: Send + Sync + 'static {
+    fn fetch_countries_within(
+        &self,
+        params: FetchTileCountriesRepositoryInput
+    ) -> Result<Vec<(Country, serde_json::Value)>, AppError>;
+
+    fn fetch_organisations_within(
+        &self,
+        params: FetchTileOrganisationsRepositoryInput
+    ) -> Result<Vec<Organisation>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct TileRepositoryImpl {
+    pool: DbPool
+}
+impl TileRepositoryImpl {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl TileRepository for TileRepositoryImpl {
+    fn fetch_countries_within(&self, params: FetchTileCountriesRepositoryInput) -> Result<Vec<(Country, serde_json::Value)>, AppError> {
+        let connection = &mut self.pool.get()?;
+        let countries = Country::get_within_bounding_box(connection, &params.bounding_box)?;
+        let ids: Vec<_> = countries.iter().map(|country| country.id).collect();
+        let mut geometries: HashMap<_, _> = Country::get_geometries_by_ids(connection, &ids, params.detail)?
+            .into_iter()
+            .collect();
+
+        let shapes = countries
+            .into_iter()
+            .filter_map(|country| geometries.remove(&country.id).map(|geometry| (country, geometry)))
+            .collect();
+
+        Ok(shapes)
+    }
+
+    fn fetch_organisations_within(&self, params: FetchTileOrganisationsRepositoryInput) -> Result<Vec<Organisation>, AppError> {
+        let connection = &mut self.pool.get()?;
+        let organisations = filter_by_bounding_box(organisations::table.into_boxed(), &params.bounding_box)
+            .order(organisations::id)
+            .limit(params.limit)
+            .load::<Organisation>(connection)?;
+
+        Ok(organisations)
+    }
+}
+
+pub struct FetchTileCountriesRepositoryInput {
+    pub bounding_box: BoundingBox,
+    pub detail: GeometryDetail,
+}
+
+pub struct FetchTileOrganisationsRepositoryInput {
+    pub bounding_box: BoundingBox,
+    pub limit: i64,
+}
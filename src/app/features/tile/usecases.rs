@@ -0,0 +1,128 @@
+use crate::constants::tiles::{BUFFER, EXTENT, MAX_ORGANISATIONS_PER_TILE, MAX_ZOOM, MIN_ORGANISATIONS_ZOOM};
+use crate::error::AppError;
+use crate::utils::geo::{polygons_from_geo_json, GeometryDetail, Polygon};
+use crate::utils::mvt::{encode_tile, Layer, PropertyValue, TileCoord};
+use super::{
+    presenters::TilePresenter,
+    repositories::{FetchTileCountriesRepositoryInput, FetchTileOrganisationsRepositoryInput, TileRepository},
+};
+use std::sync::Arc;
+use actix_web::HttpResponse;
+use serde_json::json;
+
+#[derive(Clone)]
+pub struct TileUsecase {
+    tile_repo: Arc<dyn TileRepository>,
+    tile_presenter: Arc<dyn TilePresenter>,
+}
+
+impl TileUsecase {
+    pub fn new(
+        tile_repo: Arc<dyn TileRepository>,
+        tile_presenter: Arc<dyn TilePresenter>,
+    ) -> Self {
+        Self {
+            tile_repo,
+            tile_presenter,
+        }
+    }
+
+    pub fn fetch_tile(&self, params: FetchTileUsecaseInput) -> Result<HttpResponse, AppError> {
+        let tile = TileCoord { z: params.z, x: params.x, y: params.y };
+        if tile.z > MAX_ZOOM || !tile.is_valid() {
+            return Err(AppError::UnprocessableEntity(json!({
+                "error": format!("Tile {}/{}/{} does not exist", tile.z, tile.x, tile.y),
+                "maxZoom": MAX_ZOOM
+            })));
+        }
+
+        let buffer = f64::from(BUFFER);
+        let bounding_box = tile.bounding_box(buffer / f64::from(EXTENT));
+
+        let mut countries = Layer::new("countries", EXTENT);
+        let shapes = self.tile_repo
+            .fetch_countries_within(
+                FetchTileCountriesRepositoryInput {
+                    bounding_box,
+                    detail: detail_for_zoom(tile.z),
+                }
+            )?;
+        for (country, geometry) in shapes {
+            let Some(polygons) = polygons_from_geo_json(&geometry) else {
+                continue;
+            };
+            let projected: Vec<Polygon> = polygons
+                .iter()
+                .map(|polygon| {
+                    polygon
+                        .iter()
+                        .map(|ring| ring.iter().map(|&[lon, lat]| tile.project(lat, lon, EXTENT)).collect())
+                        .collect()
+                })
+                .collect();
+
+            let mut properties = vec![
+                ("id", PropertyValue::String(country.id.to_string())),
+                ("name", PropertyValue::String(country.name)),
+            ];
+            if let Some(iso_alpha2) = country.iso_alpha2 {
+                properties.push(("isoAlpha2", PropertyValue::String(iso_alpha2)));
+            }
+            if let Some(iso_alpha3) = country.iso_alpha3 {
+                properties.push(("isoAlpha3", PropertyValue::String(iso_alpha3)));
+            }
+            countries.add_polygons(&projected, buffer, properties);
+        }
+
+        let mut organisations = Layer::new("organisations", EXTENT);
+        let within = if tile.z >= MIN_ORGANISATIONS_ZOOM {
+            self.tile_repo
+                .fetch_organisations_within(
+                    FetchTileOrganisationsRepositoryInput {
+                        bounding_box,
+                        limit: MAX_ORGANISATIONS_PER_TILE,
+                    }
+                )?
+        } else {
+            Vec::new()
+        };
+        for organisation in within {
+            let (Some(latitude), Some(longitude)) = (organisation.latitude, organisation.longitude) else {
+                continue;
+            };
+
+            let mut properties = vec![
+                ("id", PropertyValue::String(organisation.id.to_string())),
+                ("name", PropertyValue::String(organisation.name)),
+            ];
+            if let Some(organisation_type_id) = organisation.organisation_type_id {
+                properties.push(("organisationTypeId", PropertyValue::String(organisation_type_id.to_string())));
+            }
+            if let Some(location_country_id) = organisation.location_country_id {
+                properties.push(("locationCountryId", PropertyValue::String(location_country_id.to_string())));
+            }
+            organisations.add_point(tile.project(latitude, longitude, EXTENT), buffer, properties);
+        }
+
+        let response = self.tile_presenter
+            .to_mvt(encode_tile(&[countries, organisations]));
+
+        Ok(response)
+    }
+}
+
+/// Coarser precomputed shapes for the low zoom levels, where whole
+/// continents share one tile.
+fn detail_for_zoom(z: u32) -> GeometryDetail {
+    match z {
+        0..=3 => GeometryDetail::Low,
+        4..=6 => GeometryDetail::Medium,
+        _ => GeometryDetail::Full,
+    }
+}
+
+pub struct FetchTileUsecaseInput {
+    pub z: u32,
+    pub x: u32,
+    pub y: u32,
+}
@@ -3,4 +3,49 @@ pub const BIND: &str = "0.0.0.0:8080";
 pub mod env_key {
     pub const FRONTEND_ORIGIN: &str = "FRONTEND_ORIGIN";
     pub const DATABASE_URL: &str = "DATABASE_URL";
+    pub const JWT_SECRET: &str = "JWT_SECRET";
+}
+
+pub mod auth {
+    pub const ACCESS_TOKEN_TTL_MINUTES: i64 = 15;
+    pub const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+    pub const MIN_PASSWORD_LENGTH: usize = 8;
+}
+
+pub mod geo {
+    pub const DEFAULT_NEARBY_RADIUS_KM: f64 = 50.0;
+    pub const MAX_NEARBY_RADIUS_KM: f64 = 2000.0;
+    /// How far outside its location country's borders an organisation may be
+    /// placed, to absorb simplified coastlines and borders.
+    pub const LOCATION_TOLERANCE_KM: f64 = 10.0;
+    /// How close, in degrees, the borders of two countries must run to count
+    /// them as neighbours; about 1 km, to absorb slightly misaligned borders.
+    pub const BORDER_TOLERANCE_DEGREES: f64 = 0.01;
+}
+
+pub mod pagination {
+    pub const DEFAULT_LIMIT: i64 = 20;
+    /// Largest page any list endpoint returns.
+    pub const MAX_LIMIT: i64 = 100;
+}
+
+pub mod tiles {
+    pub const MAX_ZOOM: u32 = 22;
+    /// Size of a tile in its own integer coordinate space.
+    pub const EXTENT: u32 = 4096;
+    /// Geometry kept beyond the tile edges, in tile units, so polygon
+    /// outlines and point symbols do not show seams between tiles.
+    pub const BUFFER: u32 = 64;
+    /// Lowest zoom level whose tiles carry the organisations layer; below
+    /// it a tile spans too much of the world for individual points.
+    pub const MIN_ORGANISATIONS_ZOOM: u32 = 4;
+    /// Most organisations encoded into one tile.
+    pub const MAX_ORGANISATIONS_PER_TILE: i64 = 2000;
+    pub const CACHE_MAX_AGE_SECONDS: u32 = 300;
+}
+
+pub mod topology {
+    /// Borders change rarely and the topology is costly to build, so
+    /// clients and proxies may keep it longer than a tile.
+    pub const CACHE_MAX_AGE_SECONDS: u32 = 3600;
 }
@@ -1,7 +1,14 @@
 use super::schema::*;
 use crate::error::*;
+use crate::constants::geo::BORDER_TOLERANCE_DEGREES;
+use crate::utils::pagination::PageRequest;
+use crate::utils::geo::{polygons_from_geo_json, shape_metrics, shapes_share_border, simplify_geo_json, BoundingBox, GeometryDetail, Polygon};
+use diesel::dsl::sql;
+use diesel::expression::SqlLiteral;
 use diesel::prelude::*;
+use diesel::sql_types::{Jsonb, Nullable};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -15,6 +22,15 @@ pub struct Country {
     pub flag: Option<String>,
     pub capital_city: Option<String>,
     pub description: Option<String>,
+    pub iso_alpha2: Option<String>,
+    pub iso_alpha3: Option<String>,
+    pub centroid_latitude: Option<f64>,
+    pub centroid_longitude: Option<f64>,
+    pub bbox_min_latitude: Option<f64>,
+    pub bbox_min_longitude: Option<f64>,
+    pub bbox_max_latitude: Option<f64>,
+    pub bbox_max_longitude: Option<f64>,
+    pub area_km2: Option<f64>,
 }
 
 #[derive(Insertable, Clone)]
@@ -25,6 +41,8 @@ pub struct CreateCountry {
     pub flag: Option<String>,
     pub capital_city: Option<String>,
     pub description: Option<String>,
+    pub iso_alpha2: Option<String>,
+    pub iso_alpha3: Option<String>,
 }
 
 #[derive(AsChangeset)]
@@ -35,6 +53,8 @@ pub struct UpdateCountry {
     pub flag: Option<String>,
     pub capital_city: Option<String>,
     pub description: Option<String>,
+    pub iso_alpha2: Option<String>,
+    pub iso_alpha3: Option<String>,
 }
 
 impl Country {
@@ -44,41 +64,213 @@ impl Country {
     ) -> Result<Self, AppError> {
         let result = countries::table
             .find(id)
+            .select(Country::as_select())
             .get_result::<Country>(conn)?;
 
         Ok(result)
     }
 
+    /// Case-insensitive exact match on the country name.
     pub fn get_by_name(
         conn: &mut PgConnection,
         name: &str,
     ) -> Result<Self, AppError> {
+        let pattern = name.trim().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
         let result = countries::table
-            .filter(countries::name.eq(name))
+            .filter(countries::name.ilike(pattern))
+            .order(countries::name.asc())
+            .select(Country::as_select())
+            .first::<Country>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn get_by_iso_code(
+        conn: &mut PgConnection,
+        iso_code: &str,
+    ) -> Result<Self, AppError> {
+        let iso_code = iso_code.to_uppercase();
+        let result = countries::table
+            .filter(
+                countries::iso_alpha2.eq(&iso_code)
+                    .or(countries::iso_alpha3.eq(&iso_code))
+            )
+            .select(Country::as_select())
             .get_result::<Country>(conn)?;
 
         Ok(result)
     }
 
+    /// Border geometries of every country that has one, as `(id, geo_json)` pairs.
+    pub fn get_geometries(
+        conn: &mut PgConnection,
+    ) -> Result<Vec<(Uuid, serde_json::Value)>, AppError> {
+        let result = countries::table
+            .filter(countries::geo_json.is_not_null())
+            .order(countries::name.asc())
+            .select((countries::id, countries::geo_json.assume_not_null()))
+            .load::<(Uuid, serde_json::Value)>(conn)?;
+
+        Ok(result)
+    }
+
+    /// Finds the country an import record refers to: by ISO alpha-3 code, or
+    /// by name for rows loaded before ISO codes were tracked.
+    pub fn find_by_import_key(
+        conn: &mut PgConnection,
+        iso_alpha3: &str,
+        name: &str,
+    ) -> Result<Option<Self>, AppError> {
+        let result = countries::table
+            .filter(
+                countries::iso_alpha3.eq(iso_alpha3)
+                    .or(countries::iso_alpha3.is_null().and(countries::name.eq(name)))
+            )
+            .order(countries::iso_alpha3.asc().nulls_last())
+            .select(Country::as_select())
+            .first::<Country>(conn)
+            .optional()?;
+
+        Ok(result)
+    }
+
     pub fn get_all(
         conn: &mut PgConnection,
     ) -> Result<Vec<Self>, AppError> {
-        let result = countries::table.load::<Country>(conn)?;
+        let result = countries::table
+            .select(Country::as_select())
+            .load::<Country>(conn)?;
+
+        Ok(result)
+    }
+
+    /// The stored bounding box, present once the country has a geometry.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        Some(BoundingBox {
+            min_lat: self.bbox_min_latitude?,
+            min_lon: self.bbox_min_longitude?,
+            max_lat: self.bbox_max_latitude?,
+            max_lon: self.bbox_max_longitude?,
+        })
+    }
+
+    /// Every column but the geometry, which is selected as `NULL` so callers
+    /// can attach it separately at the level of detail they need.
+    pub fn without_geometry() -> CountryWithoutGeometry {
+        (
+            countries::id,
+            countries::name,
+            sql::<Nullable<Jsonb>>("NULL"),
+            countries::flag,
+            countries::capital_city,
+            countries::description,
+            countries::iso_alpha2,
+            countries::iso_alpha3,
+            countries::centroid_latitude,
+            countries::centroid_longitude,
+            countries::bbox_min_latitude,
+            countries::bbox_min_longitude,
+            countries::bbox_max_latitude,
+            countries::bbox_max_longitude,
+            countries::area_km2,
+        )
+    }
+
+    /// Countries whose stored bounding box intersects the given one, loaded
+    /// without geometry. The given box must not cross the antimeridian.
+    pub fn get_within_bounding_box(
+        conn: &mut PgConnection,
+        bounding_box: &BoundingBox,
+    ) -> Result<Vec<Self>, AppError> {
+        let overlaps_longitude = countries::bbox_min_longitude.le(countries::bbox_max_longitude)
+            .and(countries::bbox_max_longitude.ge(bounding_box.min_lon))
+            .and(countries::bbox_min_longitude.le(bounding_box.max_lon));
+        // Boxes of countries crossing the antimeridian wrap around from
+        // their minimum to their maximum longitude.
+        let wraps_into = countries::bbox_min_longitude.gt(countries::bbox_max_longitude)
+            .and(
+                countries::bbox_min_longitude.le(bounding_box.max_lon)
+                    .or(countries::bbox_max_longitude.ge(bounding_box.min_lon))
+            );
+        let result = countries::table
+            .filter(countries::bbox_max_latitude.ge(bounding_box.min_lat))
+            .filter(countries::bbox_min_latitude.le(bounding_box.max_lat))
+            .filter(overlaps_longitude.or(wraps_into))
+            .order(countries::name.asc())
+            .select(Country::without_geometry())
+            .load::<Country>(conn)?;
+
+        Ok(result)
+    }
+
+    /// Geometries of the given countries at the requested level of detail,
+    /// as `(id, geo_json)` pairs. Countries without geometry are left out.
+    pub fn get_geometries_by_ids(
+        conn: &mut PgConnection,
+        country_ids: &[Uuid],
+        detail: GeometryDetail,
+    ) -> Result<Vec<(Uuid, serde_json::Value)>, AppError> {
+        let query = countries::table
+            .filter(countries::id.eq_any(country_ids));
+        let result = match detail {
+            GeometryDetail::None => Vec::new(),
+            GeometryDetail::Low => query
+                .filter(countries::geo_json_low.is_not_null())
+                .select((countries::id, countries::geo_json_low.assume_not_null()))
+                .load::<(Uuid, serde_json::Value)>(conn)?,
+            GeometryDetail::Medium => query
+                .filter(countries::geo_json_medium.is_not_null())
+                .select((countries::id, countries::geo_json_medium.assume_not_null()))
+                .load::<(Uuid, serde_json::Value)>(conn)?,
+            GeometryDetail::Full => query
+                .filter(countries::geo_json.is_not_null())
+                .select((countries::id, countries::geo_json.assume_not_null()))
+                .load::<(Uuid, serde_json::Value)>(conn)?,
+        };
 
         Ok(result)
     }
 
+    /// Fills in the simplified geometries and shape measures of rows written
+    /// before those were derived. Returns the number of updated countries.
+    pub fn backfill_derived_geometries(
+        conn: &mut PgConnection,
+    ) -> Result<usize, AppError> {
+        let pending = countries::table
+            .filter(countries::geo_json.is_not_null())
+            .filter(
+                countries::geo_json_low.is_null()
+                    .or(countries::geo_json_medium.is_null())
+                    .or(countries::centroid_latitude.is_null())
+            )
+            .select((countries::id, countries::geo_json.assume_not_null()))
+            .load::<(Uuid, serde_json::Value)>(conn)?;
+
+        for (country_id, geo_json) in &pending {
+            diesel::update(countries::table.find(country_id))
+                .set(&DerivedGeometry::from_geo_json(geo_json))
+                .execute(conn)?;
+        }
+
+        Ok(pending.len())
+    }
+
     pub fn create(
         conn: &mut PgConnection,
         record: &CreateCountry,
     ) -> Result<Self, AppError> {
+        let derived = record.geo_json.as_ref()
+            .map(DerivedGeometry::from_geo_json)
+            .unwrap_or_default();
         let result = diesel::insert_into(countries::table)
-            .values(record)
+            .values((record, &derived))
+            .returning(Country::as_returning())
             .get_result::<Country>(conn)?;
 
         Ok(result)
     }
 
+    /// Updates the given fields; a new geometry also replaces the data derived from it.
     pub fn update (
         conn: &mut PgConnection,
         country_id: Uuid,
@@ -86,22 +278,124 @@ impl Country {
     ) -> Result<Self, AppError> {
         let c = countries::table
             .find(country_id);
-        let result = diesel::update(c)
-            .set(record)
-            .get_result::<Country>(conn)?;
+        let result = match &record.geo_json {
+            Some(geo_json) => diesel::update(c)
+                .set((record, &DerivedGeometry::from_geo_json(geo_json)))
+                .returning(Country::as_returning())
+                .get_result::<Country>(conn)?,
+            None => diesel::update(c)
+                .set(record)
+                .returning(Country::as_returning())
+                .get_result::<Country>(conn)?,
+        };
 
         Ok(result)
     }
 
+
+    /// Deletes the country together with its language and neighbour relations. Countries
+    /// still referenced by organisations or country connections are kept and
+    /// reported as a conflict.
     pub fn delete (
         conn: &mut PgConnection,
         country_id: Uuid,
     ) -> Result<(), AppError> {
-        let c = countries::table
-            .find(country_id);
-        diesel::delete(c).execute(conn)?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let organisations_count = organisations::table
+                .filter(organisations::location_country_id.eq(country_id))
+                .count()
+                .get_result::<i64>(conn)?;
+            let connections_count = countries_connections::table
+                .filter(countries_connections::location_country_id.eq(country_id))
+                .count()
+                .get_result::<i64>(conn)?;
+
+            if organisations_count > 0 || connections_count > 0 {
+                return Err(AppError::Conflict(json!({
+                    "error": "Country is still referenced and cannot be deleted",
+                    "references": {
+                        "organisations": organisations_count,
+                        "countryConnections": connections_count,
+                    }
+                })));
+            }
+
+            let relations = countries_to_languages::table
+                .filter(countries_to_languages::country_id.eq(country_id));
+            diesel::delete(relations).execute(conn)?;
+
+            let neighbours = countries_neighbours::table
+                .filter(
+                    countries_neighbours::country_id.eq(country_id)
+                        .or(countries_neighbours::neighbour_id.eq(country_id))
+                );
+            diesel::delete(neighbours).execute(conn)?;
+
+            let c = countries::table
+                .find(country_id);
+            let deleted = diesel::delete(c).execute(conn)?;
+
+            if deleted == 0 {
+                return Err(AppError::NotFound(json!({ "error": "requested record was not found" })));
+            }
+
+            Ok(())
+        })
+    }
+}
 
-        Ok(())
+pub type CountryWithoutGeometry = (
+    countries::id,
+    countries::name,
+    SqlLiteral<Nullable<Jsonb>>,
+    countries::flag,
+    countries::capital_city,
+    countries::description,
+    countries::iso_alpha2,
+    countries::iso_alpha3,
+    countries::centroid_latitude,
+    countries::centroid_longitude,
+    countries::bbox_min_latitude,
+    countries::bbox_min_longitude,
+    countries::bbox_max_latitude,
+    countries::bbox_max_longitude,
+    countries::area_km2,
+);
+
+/// Columns derived from `geo_json`, rewritten whenever the geometry changes.
+#[derive(Insertable, AsChangeset, Default)]
+#[diesel(table_name = countries, treat_none_as_null = true)]
+struct DerivedGeometry {
+    geo_json_low: Option<serde_json::Value>,
+    geo_json_medium: Option<serde_json::Value>,
+    centroid_latitude: Option<f64>,
+    centroid_longitude: Option<f64>,
+    bbox_min_latitude: Option<f64>,
+    bbox_min_longitude: Option<f64>,
+    bbox_max_latitude: Option<f64>,
+    bbox_max_longitude: Option<f64>,
+    area_km2: Option<f64>,
+}
+
+impl DerivedGeometry {
+    fn from_geo_json(geo_json: &serde_json::Value) -> Self {
+        let simplify = |detail: GeometryDetail| detail
+            .simplification()
+            .and_then(|simplification| simplify_geo_json(geo_json, simplification));
+        let metrics = polygons_from_geo_json(geo_json)
+            .and_then(|polygons| shape_metrics(&polygons));
+
+        Self {
+            geo_json_low: simplify(GeometryDetail::Low),
+            geo_json_medium: simplify(GeometryDetail::Medium),
+            centroid_latitude: metrics.as_ref().map(|m| m.centroid.0),
+            centroid_longitude: metrics.as_ref().map(|m| m.centroid.1),
+            bbox_min_latitude: metrics.as_ref().map(|m| m.bounding_box.min_lat),
+            bbox_min_longitude: metrics.as_ref().map(|m| m.bounding_box.min_lon),
+            bbox_max_latitude: metrics.as_ref().map(|m| m.bounding_box.max_lat),
+            bbox_max_longitude: metrics.as_ref().map(|m| m.bounding_box.max_lon),
+            area_km2: metrics.as_ref().map(|m| m.area_km2),
+        }
     }
 }
 
@@ -114,9 +408,81 @@ pub struct Language {
 }
 
 impl Language {
+    pub fn get_by_id(
+        conn: &mut PgConnection,
+        id: &Uuid,
+    ) -> Result<Self, AppError> {
+        let result = languages::table
+            .find(id)
+            .get_result::<Language>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn get_by_symbol(
+        conn: &mut PgConnection,
+        symbol: &str,
+    ) -> Result<Option<Self>, AppError> {
+        let result = languages::table
+            .filter(languages::symbol.eq(symbol))
+            .get_result::<Language>(conn)
+            .optional()?;
+
+        Ok(result)
+    }
+
+    /// One page of all languages ordered by name, with the id as tie-breaker.
+    pub fn get_all(
+        conn: &mut PgConnection,
+        page: &PageRequest,
+    ) -> Result<Vec<Self>, AppError> {
+        let mut query = languages::table
+            .order((languages::name.asc(), languages::id.asc()))
+            .limit(page.fetch_limit())
+            .into_boxed();
+        query = match page {
+            PageRequest::Offset { offset, .. } => query.offset(*offset),
+            PageRequest::Cursor { after, .. } => {
+                let name = after.parse_key(|key| Some(key.to_string()))?;
+                query.filter(
+                    languages::name.gt(name.clone())
+                        .or(languages::name.eq(name).and(languages::id.gt(after.id))),
+                )
+            }
+        };
+        let result = query.load::<Language>(conn)?;
+
+        Ok(result)
+    }
+
+    pub fn count(
+        conn: &mut PgConnection,
+    ) -> Result<i64, AppError> {
+        let result = languages::table
+            .count()
+            .get_result::<i64>(conn)?;
+
+        Ok(result)
+    }
+
+    /// Languages spoken in each of the given countries, as `(country_id, language)` pairs.
+    pub fn get_by_country_ids(
+        conn: &mut PgConnection,
+        country_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Self)>, AppError> {
+        let result = countries_to_languages::table
+            .inner_join(languages::table)
+            .filter(countries_to_languages::country_id.eq_any(country_ids))
+            .order(languages::name.asc())
+            .select((countries_to_languages::country_id, Language::as_select()))
+            .load::<(Uuid, Language)>(conn)?;
+
+        Ok(result)
+    }
+
     pub fn create(
         conn: &mut PgConnection,
-        record: &Language,
+        record: &CreateLanguage,
     ) -> Result<Self, AppError> {
         let result = diesel::insert_into(languages::table)
             .values(record)
@@ -139,15 +505,22 @@ impl Language {
         Ok(result)
     }
 
+    /// Deletes the language together with its country relations.
     pub fn delete(
         conn: &mut PgConnection,
         language_id: Uuid,
     ) -> Result<(), AppError> {
-        let l = languages::table
-            .find(language_id);
-        diesel::delete(l).execute(conn)?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let relations = countries_to_languages::table
+                .filter(countries_to_languages::language_id.eq(language_id));
+            diesel::delete(relations).execute(conn)?;
 
-        Ok(())
+            let l = languages::table
+                .find(language_id);
+            diesel::delete(l).execute(conn)?;
+
+            Ok(())
+        })
     }
 }
 
@@ -161,10 +534,173 @@ pub struct UpdateLanguage {
 #[derive(Insertable, Clone)]
 #[diesel(table_name = languages)]
 pub struct CreateLanguage {
-    pub name: Option<String>,
+    pub name: String,
     pub symbol: Option<String>,
 }
 
+#[derive(Debug, Queryable, Insertable, Selectable, Clone)]
+#[diesel(table_name = countries_to_languages)]
+pub struct CountryToLanguage {
+    pub country_id: Uuid,
+    pub language_id: Uuid,
+}
+
+impl CountryToLanguage {
+    /// Links the language to the country; linking an already linked pair is a no-op.
+    pub fn attach(
+        conn: &mut PgConnection,
+        record: &CountryToLanguage,
+    ) -> Result<(), AppError> {
+        diesel::insert_into(countries_to_languages::table)
+            .values(record)
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    pub fn detach(
+        conn: &mut PgConnection,
+        record: &CountryToLanguage,
+    ) -> Result<(), AppError> {
+        let r = countries_to_languages::table
+            .find((record.country_id, record.language_id));
+        let deleted = diesel::delete(r).execute(conn)?;
+
+        if deleted == 0 {
+            return Err(AppError::NotFound(json!({ "error": "Language is not attached to this country" })));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Queryable, Insertable, Selectable, Clone)]
+#[diesel(table_name = countries_neighbours)]
+pub struct CountryNeighbour {
+    pub country_id: Uuid,
+    pub neighbour_id: Uuid,
+}
+
+impl CountryNeighbour {
+    /// Neighbours of the given countries as `(country_id, neighbour)` pairs,
+    /// ordered by name. The neighbours are loaded without geometry.
+    pub fn get_by_country_ids(
+        conn: &mut PgConnection,
+        country_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Country)>, AppError> {
+        let result = countries_neighbours::table
+            .inner_join(countries::table.on(countries::id.eq(countries_neighbours::neighbour_id)))
+            .filter(countries_neighbours::country_id.eq_any(country_ids))
+            .order(countries::name.asc())
+            .select((countries_neighbours::country_id, Country::without_geometry()))
+            .load::<(Uuid, Country)>(conn)?;
+
+        Ok(result)
+    }
+
+    /// Recomputes the neighbours of one country against the stored shapes
+    /// whose bounding box comes within the border tolerance of its own.
+    /// Returns the number of neighbours found.
+    pub fn refresh_for(
+        conn: &mut PgConnection,
+        country_id: Uuid,
+    ) -> Result<usize, AppError> {
+        let shapes = Self::load_candidate_shapes(conn, country_id)?;
+        let neighbour_ids: Vec<Uuid> = match shapes.iter().find(|(id, _)| *id == country_id) {
+            Some((_, shape)) => shapes
+                .iter()
+                .filter(|(id, other)| *id != country_id && shapes_share_border(shape, other, BORDER_TOLERANCE_DEGREES))
+                .map(|(id, _)| *id)
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let existing = countries_neighbours::table
+            .filter(
+                countries_neighbours::country_id.eq(country_id)
+                    .or(countries_neighbours::neighbour_id.eq(country_id))
+            );
+        diesel::delete(existing).execute(conn)?;
+
+        let records: Vec<CountryNeighbour> = neighbour_ids
+            .iter()
+            .flat_map(|&neighbour_id| Self::pair(country_id, neighbour_id))
+            .collect();
+        diesel::insert_into(countries_neighbours::table)
+            .values(&records)
+            .execute(conn)?;
+
+        Ok(neighbour_ids.len())
+    }
+
+    /// Recomputes the neighbours of every country. Returns the number of
+    /// bordering pairs.
+    pub fn rebuild_all(
+        conn: &mut PgConnection,
+    ) -> Result<usize, AppError> {
+        let shapes = Self::load_shapes(conn)?;
+        let mut records = Vec::new();
+        for (index, (country_id, shape)) in shapes.iter().enumerate() {
+            for (neighbour_id, other) in &shapes[index + 1..] {
+                if shapes_share_border(shape, other, BORDER_TOLERANCE_DEGREES) {
+                    records.extend(Self::pair(*country_id, *neighbour_id));
+                }
+            }
+        }
+
+        diesel::delete(countries_neighbours::table).execute(conn)?;
+        diesel::insert_into(countries_neighbours::table)
+            .values(&records)
+            .execute(conn)?;
+
+        Ok(records.len() / 2)
+    }
+
+    /// Shapes of the country and of the countries that may border it.
+    fn load_candidate_shapes(conn: &mut PgConnection, country_id: Uuid) -> Result<Vec<(Uuid, Vec<Polygon>)>, AppError> {
+        let country = countries::table
+            .find(country_id)
+            .select(Country::without_geometry())
+            .first::<Country>(conn)
+            .optional()?;
+        let Some(bounding_box) = country.and_then(|country| country.bounding_box()) else {
+            return Ok(Vec::new());
+        };
+
+        let mut ids = Vec::new();
+        for part in bounding_box.grown_and_split(BORDER_TOLERANCE_DEGREES) {
+            ids.extend(Country::get_within_bounding_box(conn, &part)?.into_iter().map(|country| country.id));
+        }
+        ids.sort();
+        ids.dedup();
+
+        let shapes = Country::get_geometries_by_ids(conn, &ids, GeometryDetail::Full)?
+            .into_iter()
+            .filter_map(|(id, geo_json)| polygons_from_geo_json(&geo_json).map(|polygons| (id, polygons)))
+            .collect();
+
+        Ok(shapes)
+    }
+
+    fn load_shapes(conn: &mut PgConnection) -> Result<Vec<(Uuid, Vec<Polygon>)>, AppError> {
+        let shapes = Country::get_geometries(conn)?
+            .into_iter()
+            .filter_map(|(id, geo_json)| polygons_from_geo_json(&geo_json).map(|polygons| (id, polygons)))
+            .collect();
+
+        Ok(shapes)
+    }
+
+    /// Both directions of a bordering pair.
+    fn pair(country_id: Uuid, neighbour_id: Uuid) -> [CountryNeighbour; 2] {
+        [
+            CountryNeighbour { country_id, neighbour_id },
+            CountryNeighbour { country_id: neighbour_id, neighbour_id: country_id },
+        ]
+    }
+}
+
 // TODO: TMP ToSchema derive for documentation purposes
 #[derive(Debug, Serialize, Deserialize, Queryable, Insertable, Selectable, Clone, ToSchema)]
 #[diesel(table_name = organisation_types)]
@@ -202,10 +738,57 @@ impl OrganisationType {
         conn: &mut PgConnection,
     ) -> Result<Vec<Self>, AppError> {
         let result = organisation_types::table
+            .order(organisation_types::type_.asc())
             .load::<OrganisationType>(conn)?;
 
         Ok(result)
     }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        org_type_id: Uuid,
+        record: &UpdateOrganisationType,
+    ) -> Result<Self, AppError> {
+        let t = organisation_types::table
+            .find(org_type_id);
+        let result = diesel::update(t)
+            .set(record)
+            .get_result::<OrganisationType>(conn)?;
+
+        Ok(result)
+    }
+
+    /// Deletes the organisation type unless organisations still use it.
+    pub fn delete(
+        conn: &mut PgConnection,
+        org_type_id: Uuid,
+    ) -> Result<(), AppError> {
+        conn.transaction::<_, AppError, _>(|conn| {
+            let organisations_count = organisations::table
+                .filter(organisations::organisation_type_id.eq(org_type_id))
+                .count()
+                .get_result::<i64>(conn)?;
+
+            if organisations_count > 0 {
+                return Err(AppError::Conflict(json!({
+                    "error": "Organisation type is still in use and cannot be deleted",
+                    "references": {
+                        "organisations": organisations_count,
+                    }
+                })));
+            }
+
+            let t = organisation_types::table
+                .find(org_type_id);
+            let deleted = diesel::delete(t).execute(conn)?;
+
+            if deleted == 0 {
+                return Err(AppError::NotFound(json!({ "error": "requested record was not found" })));
+            }
+
+            Ok(())
+        })
+    }
 }
 
 #[derive(Insertable, Clone)]
@@ -215,3 +798,11 @@ pub struct CreateOrganisationType {
     pub org_type: String,
     pub color: Option<String>,
 }
+
+#[derive(AsChangeset)]
+#[diesel(table_name = organisation_types)]
+pub struct UpdateOrganisationType {
+    #[diesel(column_name = type_)]
+    pub org_type: Option<String>,
+    pub color: Option<String>,
+}
@@ -7,6 +7,7 @@ diesel::table! {
         origin_country_connection_id -> Nullable<Uuid>,
         link -> Nullable<Text>,
         info -> Nullable<Text>,
+        created_at -> Timestamp,
     }
 }
 
@@ -18,6 +19,17 @@ diesel::table! {
         flag -> Nullable<Text>,
         capital_city -> Nullable<Text>,
         description -> Nullable<Text>,
+        iso_alpha2 -> Nullable<Text>,
+        iso_alpha3 -> Nullable<Text>,
+        geo_json_low -> Nullable<Jsonb>,
+        geo_json_medium -> Nullable<Jsonb>,
+        centroid_latitude -> Nullable<Float8>,
+        centroid_longitude -> Nullable<Float8>,
+        bbox_min_latitude -> Nullable<Float8>,
+        bbox_min_longitude -> Nullable<Float8>,
+        bbox_max_latitude -> Nullable<Float8>,
+        bbox_max_longitude -> Nullable<Float8>,
+        area_km2 -> Nullable<Float8>,
     }
 }
 
@@ -31,6 +43,13 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    countries_neighbours (country_id, neighbour_id) {
+        country_id -> Uuid,
+        neighbour_id -> Uuid,
+    }
+}
+
 diesel::table! {
     countries_to_languages (country_id, language_id) {
         country_id -> Uuid,
@@ -67,8 +86,29 @@ diesel::table! {
         organisation_type_id -> Nullable<Uuid>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
-        latitude -> Nullable<Numeric>,
-        longitude -> Nullable<Numeric>,
+        latitude -> Nullable<Float8>,
+        longitude -> Nullable<Float8>,
+    }
+}
+
+diesel::table! {
+    refresh_tokens (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        expires_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    users (id) {
+        id -> Uuid,
+        email -> Text,
+        password_hash -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        role -> Text,
     }
 }
 
@@ -78,13 +118,17 @@ diesel::joinable!(countries_to_languages -> countries (country_id));
 diesel::joinable!(countries_to_languages -> languages (language_id));
 diesel::joinable!(organisations -> countries (location_country_id));
 diesel::joinable!(organisations -> organisation_types (organisation_type_id));
+diesel::joinable!(refresh_tokens -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     chats,
     countries,
     countries_connections,
+    countries_neighbours,
     countries_to_languages,
     languages,
     organisation_types,
     organisations,
+    refresh_tokens,
+    users,
 );
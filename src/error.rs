@@ -7,6 +7,8 @@ use diesel::result::{Error as DieselError, DatabaseErrorKind};
 use diesel::r2d2::{Error as R2D2Error, PoolError};
 use serde_json::{json, Value as JsonValue};
 use uuid::Error as UuidError;
+use bcrypt::BcryptError;
+use jsonwebtoken::errors::Error as JwtError;
 use utoipa::ToSchema;
 
 #[derive(Error, ToSchema, Debug)]
@@ -23,6 +25,10 @@ pub enum AppError {
     #[error("Not Found: {}", _0)]
     NotFound(JsonValue),
     
+    // 409
+    #[error("Conflict: {}", _0)]
+    Conflict(JsonValue),
+    
     // 422
     #[error("Unprocessable Entity: {}", _0)]
     UnprocessableEntity(JsonValue),
@@ -38,6 +44,7 @@ impl ResponseError for AppError {
             AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             AppError::Forbidden(_) => StatusCode::FORBIDDEN,
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
             AppError::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
             AppError::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
         }
@@ -48,6 +55,7 @@ impl ResponseError for AppError {
             AppError::Unauthorized(msg) => HttpResponse::Unauthorized().json(msg),
             AppError::Forbidden(msg) => HttpResponse::Forbidden().json(msg),
             AppError::NotFound(msg) => HttpResponse::NotFound().json(msg),
+            AppError::Conflict(msg) => HttpResponse::Conflict().json(msg),
             AppError::UnprocessableEntity(msg) => HttpResponse::UnprocessableEntity().json(msg),
             AppError::InternalServerError => {
                 HttpResponse::InternalServerError().json("Internal Server Error")
@@ -59,13 +67,12 @@ impl ResponseError for AppError {
 impl From<DieselError> for AppError {
     fn from(error: DieselError) -> Self {
         match error {
-            DieselError::DatabaseError(kind, info) => {
-                if let DatabaseErrorKind::UniqueViolation = kind {
-                    let message = info.details().unwrap_or_else(|| info.message()).to_string();
-                    AppError::UnprocessableEntity(json!({ "error": message }))
-                } else {
-                    AppError::InternalServerError
-                }
+            DieselError::DatabaseError(
+                DatabaseErrorKind::UniqueViolation | DatabaseErrorKind::ForeignKeyViolation,
+                info,
+            ) => {
+                let message = info.details().unwrap_or_else(|| info.message()).to_string();
+                AppError::UnprocessableEntity(json!({ "error": message }))
             }
             DieselError::NotFound => {
                 AppError::NotFound(json!({ "error": "requested record was not found" }))
@@ -98,3 +105,17 @@ impl From<UuidError> for AppError {
         }))
     }
 }
+
+impl From<BcryptError> for AppError {
+    fn from(_error: BcryptError) -> Self {
+        AppError::InternalServerError
+    }
+}
+
+impl From<JwtError> for AppError {
+    fn from(value: JwtError) -> Self {
+        // Why a token was rejected is only logged, not told to the client.
+        log::info!("Rejected token: {}", value);
+        AppError::Unauthorized(json!({ "error": "Invalid or expired token" }))
+    }
+}
@@ -1,6 +1,6 @@
 mod constants;
 mod app;
-mod error;
+pub mod error;
 pub mod data;
 pub mod utils;
 
@@ -10,7 +10,8 @@ use crate::app::drivers::middlewares::{
 use actix_web::middleware::Logger;
 use actix_web::{web, App, HttpServer};
 use dotenv::dotenv;
-use utoipa::OpenApi;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 
 #[derive(OpenApi)]
@@ -26,7 +27,21 @@ use utoipa_swagger_ui::SwaggerUi;
     paths(
         app::features::healthcheck::controllers::index,
         app::features::common::controllers::fetch_all_countries,
+        app::features::common::controllers::fetch_country,
+        app::features::common::controllers::fetch_country_by_code,
+        app::features::common::controllers::fetch_country_by_name,
+        app::features::common::controllers::fetch_country_at,
+        app::features::common::controllers::fetch_countries_topology,
+        app::features::common::controllers::create_country,
+        app::features::common::controllers::update_country,
+        app::features::common::controllers::delete_country,
+        app::features::common::controllers::fetch_organisation_types,
+        app::features::common::controllers::fetch_organisation_type,
+        app::features::common::controllers::create_organisation_type,
+        app::features::common::controllers::update_organisation_type,
+        app::features::common::controllers::delete_organisation_type,
         app::features::organisation::controllers::list,
+        app::features::organisation::controllers::nearby,
         app::features::organisation::controllers::fetch,
         app::features::organisation::controllers::create,
         app::features::organisation::controllers::delete,
@@ -36,32 +51,116 @@ use utoipa_swagger_ui::SwaggerUi;
         app::features::country_connection::controllers::create,
         app::features::country_connection::controllers::delete,
         app::features::country_connection::controllers::update,
+        app::features::auth::controllers::register,
+        app::features::auth::controllers::login,
+        app::features::auth::controllers::refresh,
+        app::features::auth::controllers::logout,
+        app::features::auth::controllers::list_users,
+        app::features::auth::controllers::update_user_role,
+        app::features::chat::controllers::list,
+        app::features::chat::controllers::fetch,
+        app::features::chat::controllers::create,
+        app::features::chat::controllers::update,
+        app::features::chat::controllers::delete,
+        app::features::language::controllers::list,
+        app::features::language::controllers::fetch,
+        app::features::language::controllers::create,
+        app::features::language::controllers::update,
+        app::features::language::controllers::delete,
+        app::features::language::controllers::attach,
+        app::features::language::controllers::detach,
+        app::features::tile::controllers::fetch_tile,
+        app::features::search::controllers::search,
     ),
     components(
         schemas(
             app::features::common::presenters::CountryContent,
+            app::features::common::presenters::MultipleCountriesResponse,
+            app::features::common::presenters::CentroidContent,
+            app::features::common::presenters::CountrySummaryContent,
+            utils::geojson::Feature,
+            utils::geojson::FeatureCollection,
             app::features::common::presenters::OrganisationTypeContent,
+            app::features::common::requests::CreateCountryRequest,
+            app::features::common::requests::UpdateCountryRequest,
+            app::features::common::requests::CreateOrganisationTypeRequest,
+            app::features::common::requests::UpdateOrganisationTypeRequest,
+            app::features::common::requests::CountriesListQueryParams,
+            app::features::common::requests::CountriesTopologyQueryParams,
+            app::features::common::requests::CountryQueryParams,
+            app::features::common::requests::CountryAtQueryParams,
             app::features::organisation::requests::OrganisationsListQueryRequest,
             app::features::organisation::requests::CreateOrganisationRequest,
             app::features::organisation::requests::UpdateOrganisationRequest,
             app::features::organisation::presenters::OrganisationContent,
+            app::features::organisation::presenters::SearchMatchContent,
             app::features::organisation::presenters::MultipleOrganisationsResponse,
+            app::features::organisation::presenters::NearbyOrganisationContent,
+            app::features::organisation::presenters::NearbyOrganisationsResponse,
+            app::features::organisation::requests::NearbyOrganisationsQueryRequest,
             app::features::country_connection::requests::CreateCountryConnectionRequest,
             app::features::country_connection::requests::UpdateCountryConnectionRequest,
             app::features::country_connection::requests::CountryConnectionsListQueryParams,
             app::features::country_connection::presenters::CountryConnectionContent,
             app::features::country_connection::presenters::MultipleCountryConnectionsResponse,
+            app::features::auth::requests::RegisterRequest,
+            app::features::auth::requests::LoginRequest,
+            app::features::auth::requests::RefreshTokenRequest,
+            app::features::auth::requests::UpdateUserRoleRequest,
+            app::features::auth::requests::UsersListQueryParams,
+            app::features::auth::permissions::Role,
+            app::features::chat::requests::CreateChatRequest,
+            app::features::chat::requests::UpdateChatRequest,
+            app::features::chat::requests::ChatsListQueryParams,
+            app::features::chat::presenters::ChatContent,
+            app::features::chat::presenters::MultipleChatsResponse,
+            app::features::language::requests::CreateLanguageRequest,
+            app::features::language::requests::UpdateLanguageRequest,
+            app::features::language::requests::CountryLanguageRequest,
+            app::features::language::requests::LanguagesListQueryParams,
+            app::features::language::presenters::LanguageContent,
+            app::features::language::presenters::MultipleLanguagesResponse,
+            app::features::auth::presenters::UserContent,
+            app::features::auth::presenters::MultipleUsersResponse,
+            app::features::auth::presenters::TokenPairContent,
+            app::features::search::entities::SearchKind,
+            app::features::search::requests::SearchQueryParams,
+            app::features::search::presenters::SearchHitContent,
+            app::features::search::presenters::SearchResponse,
         )
     ),
+    modifiers(&SecurityAddon),
     tags(
         (name = "Healthcheck", description = "Healthcheck related endpoints"),
         (name = "Common", description = "Common endpoints like countries, etc."),
         (name = "Organisation", description = "Organisation related endpoints"),
-        (name = "CountryConnection", description = "CountryConnection related endpoints")
+        (name = "CountryConnection", description = "CountryConnection related endpoints"),
+        (name = "Auth", description = "Registration, login and token management"),
+        (name = "Chat", description = "Community chats related endpoints"),
+        (name = "Language", description = "Languages and country-language relations"),
+        (name = "Tiles", description = "Vector tiles of countries and organisations for web maps"),
+        (name = "Search", description = "Search across countries, organisations, country connections and chats")
     )
 )]
 pub struct ApiDoc;
 
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv().ok();
@@ -97,6 +196,11 @@ async fn main() -> std::io::Result<()> {
                     .configure(app::features::common::config::configure_services)
                     .configure(app::features::organisation::config::configure_services)
                     .configure(app::features::country_connection::config::configure_services)
+                    .configure(app::features::auth::config::configure_services)
+                    .configure(app::features::chat::config::configure_services)
+                    .configure(app::features::language::config::configure_services)
+                    .configure(app::features::tile::config::configure_services)
+                    .configure(app::features::search::config::configure_services)
             )
     })
     .bind(constants::BIND)?
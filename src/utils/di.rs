@@ -14,6 +14,31 @@ use crate::app::features::country_connection::{
     presenters::CountryConnectionPresenterImpl,
     usecases::CountryConnectionUsecase,
 };
+use crate::app::features::auth::{
+    presenters::AuthPresenterImpl,
+    repositories::AuthRepositoryImpl,
+    usecases::AuthUsecase,
+};
+use crate::app::features::chat::{
+    presenters::ChatPresenterImpl,
+    repositories::ChatRepositoryImpl,
+    usecases::ChatUsecase,
+};
+use crate::app::features::language::{
+    presenters::LanguagePresenterImpl,
+    repositories::LanguageRepositoryImpl,
+    usecases::LanguageUsecase,
+};
+use crate::app::features::tile::{
+    presenters::TilePresenterImpl,
+    repositories::TileRepositoryImpl,
+    usecases::TileUsecase,
+};
+use crate::app::features::search::{
+    presenters::SearchPresenterImpl,
+    repositories::SearchRepositoryImpl,
+    usecases::SearchUsecase,
+};
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -21,6 +46,11 @@ pub struct DiContainer {
     pub organisation_usecase: OrganisationUsecase,
     pub common_usecase: CommonUsecase,
     pub country_connection_usecase: CountryConnectionUsecase,
+    pub auth_usecase: AuthUsecase,
+    pub chat_usecase: ChatUsecase,
+    pub language_usecase: LanguageUsecase,
+    pub tile_usecase: TileUsecase,
+    pub search_usecase: SearchUsecase,
 }
 
 impl DiContainer {
@@ -34,6 +64,21 @@ impl DiContainer {
         let country_connection_repo = CountryConnectionRepositoryImpl::new(pool.clone());
         let country_connection_presenter = CountryConnectionPresenterImpl::new();
 
+        let auth_repo = AuthRepositoryImpl::new(pool.clone());
+        let auth_presenter = AuthPresenterImpl::new();
+
+        let chat_repo = ChatRepositoryImpl::new(pool.clone());
+        let chat_presenter = ChatPresenterImpl::new();
+
+        let language_repo = LanguageRepositoryImpl::new(pool.clone());
+        let language_presenter = LanguagePresenterImpl::new();
+
+        let tile_repo = TileRepositoryImpl::new(pool.clone());
+        let tile_presenter = TilePresenterImpl::new();
+
+        let search_repo = SearchRepositoryImpl::new(pool.clone());
+        let search_presenter = SearchPresenterImpl::new();
+
         Self {
             organisation_usecase: OrganisationUsecase::new(
                 Arc::new(organisation_repo.clone()),
@@ -47,6 +92,26 @@ impl DiContainer {
                 Arc::new(country_connection_repo.clone()),
                 Arc::new(country_connection_presenter.clone()),
             ),
+            auth_usecase: AuthUsecase::new(
+                Arc::new(auth_repo.clone()),
+                Arc::new(auth_presenter.clone()),
+            ),
+            chat_usecase: ChatUsecase::new(
+                Arc::new(chat_repo.clone()),
+                Arc::new(chat_presenter.clone()),
+            ),
+            language_usecase: LanguageUsecase::new(
+                Arc::new(language_repo.clone()),
+                Arc::new(language_presenter.clone()),
+            ),
+            tile_usecase: TileUsecase::new(
+                Arc::new(tile_repo.clone()),
+                Arc::new(tile_presenter.clone()),
+            ),
+            search_usecase: SearchUsecase::new(
+                Arc::new(search_repo.clone()),
+                Arc::new(search_presenter.clone()),
+            ),
         }
     }
 }
@@ -0,0 +1,696 @@
+//! Spherical geometry helpers for WGS84 coordinates given in decimal degrees.
+
+use crate::error::AppError;
+use serde_json::json;
+
+/// Mean Earth radius in kilometres.
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Great-circle distance between two points, using the haversine formula.
+pub fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+}
+
+pub fn is_valid_latitude(lat: f64) -> bool {
+    (-90.0..=90.0).contains(&lat)
+}
+
+pub fn is_valid_longitude(lon: f64) -> bool {
+    (-180.0..=180.0).contains(&lon)
+}
+
+/// Latitude/longitude box. A box whose `min_lon` is greater than its
+/// `max_lon` crosses the antimeridian, e.g. `170..-170` spans 20 degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_lat: f64,
+    pub min_lon: f64,
+    pub max_lat: f64,
+    pub max_lon: f64,
+}
+
+impl BoundingBox {
+    /// Parses a `minLon,minLat,maxLon,maxLat` box as used by GeoJSON and most
+    /// map libraries. A `minLon` east of `maxLon` means the box crosses the
+    /// antimeridian.
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        let invalid = || AppError::UnprocessableEntity(json!({
+            "error": format!("Invalid bbox '{}', expected minLon,minLat,maxLon,maxLat in degrees", value)
+        }));
+        let parts = value
+            .split(',')
+            .map(|part| part.trim().parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+        let [min_lon, min_lat, max_lon, max_lat] = parts[..] else {
+            return Err(invalid());
+        };
+
+        let valid = is_valid_longitude(min_lon)
+            && is_valid_longitude(max_lon)
+            && is_valid_latitude(min_lat)
+            && is_valid_latitude(max_lat)
+            && min_lat <= max_lat;
+        if !valid {
+            return Err(invalid());
+        }
+
+        Ok(Self { min_lat, min_lon, max_lat, max_lon })
+    }
+
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.min_lon > self.max_lon
+    }
+
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        let within_lon = if self.crosses_antimeridian() {
+            lon >= self.min_lon || lon <= self.max_lon
+        } else {
+            lon >= self.min_lon && lon <= self.max_lon
+        };
+
+        within_lon && lat >= self.min_lat && lat <= self.max_lat
+    }
+
+    /// The box grown by `margin` degrees on every side, clamped to the
+    /// valid ranges, as boxes that do not cross the antimeridian.
+    pub fn grown_and_split(&self, margin: f64) -> Vec<Self> {
+        let (min_lat, max_lat) = ((self.min_lat - margin).max(-90.0), (self.max_lat + margin).min(90.0));
+        let part = |min_lon: f64, max_lon: f64| Self {
+            min_lat,
+            min_lon: min_lon.max(-180.0),
+            max_lat,
+            max_lon: max_lon.min(180.0),
+        };
+
+        if self.crosses_antimeridian() {
+            vec![part(self.min_lon - margin, 180.0), part(-180.0, self.max_lon + margin)]
+        } else {
+            vec![part(self.min_lon - margin, self.max_lon + margin)]
+        }
+    }
+}
+
+/// Normalises a longitude into `-180..=180`.
+pub fn wrap_longitude(lon: f64) -> f64 {
+    if (-180.0..=180.0).contains(&lon) {
+        return lon;
+    }
+
+    let wrapped = (lon + 180.0).rem_euclid(360.0) - 180.0;
+    if wrapped == -180.0 && lon > 0.0 { 180.0 } else { wrapped }
+}
+
+/// A closed ring of `[lon, lat]` positions.
+pub type Ring = Vec<[f64; 2]>;
+
+/// An outer ring followed by any number of holes.
+pub type Polygon = Vec<Ring>;
+
+/// Reads the polygons of a GeoJSON `Polygon` or `MultiPolygon` geometry.
+/// Returns `None` for any other geometry type or malformed coordinates.
+pub fn polygons_from_geo_json(geometry: &serde_json::Value) -> Option<Vec<Polygon>> {
+    let coordinates = geometry.get("coordinates")?;
+
+    match geometry.get("type")?.as_str()? {
+        "Polygon" => Some(vec![parse_polygon(coordinates)?]),
+        "MultiPolygon" => coordinates.as_array()?.iter().map(parse_polygon).collect(),
+        _ => None,
+    }
+}
+
+fn parse_polygon(value: &serde_json::Value) -> Option<Polygon> {
+    value.as_array()?.iter().map(parse_ring).collect()
+}
+
+fn parse_ring(value: &serde_json::Value) -> Option<Ring> {
+    value
+        .as_array()?
+        .iter()
+        .map(|position| {
+            let position = position.as_array()?;
+            Some([position.first()?.as_f64()?, position.get(1)?.as_f64()?])
+        })
+        .collect()
+}
+
+/// Even-odd point-in-polygon test; a point inside a hole is outside the polygon.
+pub fn polygon_contains(polygon: &Polygon, lat: f64, lon: f64) -> bool {
+    polygon
+        .iter()
+        .filter(|ring| ring_contains(ring, lat, lon))
+        .count()
+        % 2
+        == 1
+}
+
+fn ring_contains(ring: &Ring, lat: f64, lon: f64) -> bool {
+    let mut inside = false;
+
+    for (a, b) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+        let ([a_lon, a_lat], [b_lon, b_lat]) = (*a, *b);
+        if (a_lat > lat) != (b_lat > lat) {
+            let crossing_lon = a_lon + (lat - a_lat) / (b_lat - a_lat) * (b_lon - a_lon);
+            if lon < crossing_lon {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+pub fn polygons_contain(polygons: &[Polygon], lat: f64, lon: f64) -> bool {
+    polygons.iter().any(|polygon| polygon_contains(polygon, lat, lon))
+}
+
+/// Approximate distance from a point to the nearest polygon edge, using a
+/// local equirectangular projection that is accurate over short distances.
+pub fn distance_to_boundary_km(polygons: &[Polygon], lat: f64, lon: f64) -> f64 {
+    let cos_lat = lat.to_radians().cos();
+    let project = |[p_lon, p_lat]: [f64; 2]| {
+        let x = (wrap_longitude(p_lon - lon) * cos_lat).to_radians() * EARTH_RADIUS_KM;
+        let y = (p_lat - lat).to_radians() * EARTH_RADIUS_KM;
+        (x, y)
+    };
+
+    polygons
+        .iter()
+        .flatten()
+        .flat_map(|ring| ring.windows(2))
+        .map(|edge| {
+            let (ax, ay) = project(edge[0]);
+            let (bx, by) = project(edge[1]);
+            let (dx, dy) = (bx - ax, by - ay);
+            let length_squared = dx * dx + dy * dy;
+            let t = if length_squared == 0.0 {
+                0.0
+            } else {
+                (-(ax * dx + ay * dy) / length_squared).clamp(0.0, 1.0)
+            };
+
+            (ax + t * dx).hypot(ay + t * dy)
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Measures derived from a country shape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeMetrics {
+    /// Area-weighted centroid as `(lat, lon)`. When it falls outside the
+    /// shape, e.g. for crescent-shaped countries, an interior point is used
+    /// instead so it can always serve as a label position.
+    pub centroid: (f64, f64),
+    pub bounding_box: BoundingBox,
+    pub area_km2: f64,
+}
+
+/// Centroid, bounding box and area of a set of polygons. Shapes crossing the
+/// antimeridian are measured with their western longitudes shifted by 360
+/// degrees, so the box wraps around instead of spanning the whole globe.
+pub fn shape_metrics(polygons: &[Polygon]) -> Option<ShapeMetrics> {
+    let polygons = unwrap_antimeridian(polygons);
+    let points = || polygons.iter().flatten().flatten();
+    let (min_lon, max_lon) = points().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), p| (min.min(p[0]), max.max(p[0])));
+    let (min_lat, max_lat) = points().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), p| (min.min(p[1]), max.max(p[1])));
+    if !min_lon.is_finite() || !min_lat.is_finite() {
+        return None;
+    }
+
+    let area_km2 = polygons
+        .iter()
+        .map(|polygon| {
+            let mut rings = polygon.iter().map(ring_area_km2);
+            let outer = rings.next().unwrap_or(0.0);
+            (outer - rings.sum::<f64>()).max(0.0)
+        })
+        .sum();
+
+    let (lat, lon) = label_point(&polygons).unwrap_or((min_lat, min_lon));
+
+    Some(ShapeMetrics {
+        centroid: (lat, wrap_longitude(lon)),
+        bounding_box: BoundingBox {
+            min_lat,
+            min_lon: wrap_longitude(min_lon),
+            max_lat,
+            max_lon: wrap_longitude(max_lon),
+        },
+        area_km2,
+    })
+}
+
+/// Shifts western longitudes east by 360 degrees when that makes the shape
+/// narrower, which is the case for shapes split at the antimeridian.
+fn unwrap_antimeridian(polygons: &[Polygon]) -> Vec<Polygon> {
+    let span = |shift: bool| {
+        let (min, max) = polygons
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|p| if shift && p[0] < 0.0 { p[0] + 360.0 } else { p[0] })
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), lon| (min.min(lon), max.max(lon)));
+        max - min
+    };
+
+    if span(true) >= span(false) {
+        return polygons.to_vec();
+    }
+
+    polygons
+        .iter()
+        .map(|polygon| {
+            polygon
+                .iter()
+                .map(|ring| ring.iter().map(|&[lon, lat]| [if lon < 0.0 { lon + 360.0 } else { lon }, lat]).collect())
+                .collect()
+        })
+        .collect()
+}
+
+/// Area of a ring on the sphere, ignoring its orientation.
+fn ring_area_km2(ring: &Ring) -> f64 {
+    let sum: f64 = ring
+        .windows(2)
+        .map(|edge| {
+            let [lon1, lat1] = edge[0];
+            let [lon2, lat2] = edge[1];
+            (lon2 - lon1).to_radians() * (2.0 + lat1.to_radians().sin() + lat2.to_radians().sin())
+        })
+        .sum();
+
+    (sum * EARTH_RADIUS_KM * EARTH_RADIUS_KM / 2.0).abs()
+}
+
+/// Planar signed area and centroid of a ring in degrees.
+fn ring_centroid(ring: &Ring) -> (f64, [f64; 2]) {
+    let (mut area, mut cx, mut cy) = (0.0, 0.0, 0.0);
+    for edge in ring.windows(2) {
+        let ([x1, y1], [x2, y2]) = (edge[0], edge[1]);
+        let cross = x1 * y2 - x2 * y1;
+        area += cross;
+        cx += (x1 + x2) * cross;
+        cy += (y1 + y2) * cross;
+    }
+
+    if area == 0.0 {
+        return (0.0, ring.first().copied().unwrap_or([0.0, 0.0]));
+    }
+
+    (area / 2.0, [cx / (3.0 * area), cy / (3.0 * area)])
+}
+
+/// The area-weighted centroid as `(lat, lon)`, or a point inside the largest
+/// polygon when the centroid lies outside the shape.
+fn label_point(polygons: &[Polygon]) -> Option<(f64, f64)> {
+    let (mut weight, mut cx, mut cy) = (0.0, 0.0, 0.0);
+    let mut largest: Option<(f64, &Polygon)> = None;
+    for polygon in polygons {
+        let mut polygon_area = 0.0;
+        for (index, ring) in polygon.iter().enumerate() {
+            let (area, [x, y]) = ring_centroid(ring);
+            // Holes count negatively, whatever their orientation.
+            let area = if index == 0 { area.abs() } else { -area.abs() };
+            polygon_area += area;
+            weight += area;
+            cx += x * area;
+            cy += y * area;
+        }
+        if largest.is_none_or(|(area, _)| polygon_area > area) {
+            largest = Some((polygon_area, polygon));
+        }
+    }
+
+    if weight > 0.0 && polygons_contain(polygons, cy / weight, cx / weight) {
+        return Some((cy / weight, cx / weight));
+    }
+
+    let (_, polygon) = largest?;
+    interior_point(polygon)
+}
+
+/// Midpoint of the widest horizontal span through the middle of a polygon.
+fn interior_point(polygon: &Polygon) -> Option<(f64, f64)> {
+    let outer = polygon.first()?;
+    let (min_lat, max_lat) = outer.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), p| (min.min(p[1]), max.max(p[1])));
+    let lat = (min_lat + max_lat) / 2.0;
+
+    let mut crossings: Vec<f64> = polygon
+        .iter()
+        .flat_map(|ring| ring.windows(2))
+        .filter(|edge| (edge[0][1] > lat) != (edge[1][1] > lat))
+        .map(|edge| {
+            let ([x1, y1], [x2, y2]) = (edge[0], edge[1]);
+            x1 + (lat - y1) * (x2 - x1) / (y2 - y1)
+        })
+        .collect();
+    crossings.sort_by(f64::total_cmp);
+
+    crossings
+        .chunks_exact(2)
+        .max_by(|a, b| (a[1] - a[0]).total_cmp(&(b[1] - b[0])))
+        .map(|span| (lat, (span[0] + span[1]) / 2.0))
+        .or_else(|| outer.first().map(|&[lon, lat]| (lat, lon)))
+}
+
+/// Whether two shapes share a border: at least two vertices of one lie within
+/// `tolerance` degrees of the edges of the other. A single touching corner is
+/// not enough.
+pub fn shapes_share_border(a: &[Polygon], b: &[Polygon], tolerance: f64) -> bool {
+    let (Some(a_box), Some(b_box)) = (planar_bounds(a, tolerance), planar_bounds(b, tolerance)) else {
+        return false;
+    };
+    if a_box[0] > b_box[2] || b_box[0] > a_box[2] || a_box[1] > b_box[3] || b_box[1] > a_box[3] {
+        return false;
+    }
+
+    let touching = |from: &[Polygon], to: &[Polygon], from_box: [f64; 4], to_box: [f64; 4]| {
+        let within = |box_: [f64; 4], p: [f64; 2]| p[0] >= box_[0] && p[0] <= box_[2] && p[1] >= box_[1] && p[1] <= box_[3];
+        let edges: Vec<_> = to
+            .iter()
+            .flatten()
+            .flat_map(|ring| ring.windows(2))
+            .filter(|edge| within(from_box, edge[0]) || within(from_box, edge[1]))
+            .collect();
+
+        from.iter()
+            .flatten()
+            // The closing position repeats the first one.
+            .flat_map(|ring| &ring[..ring.len().saturating_sub(1)])
+            .filter(|&&p| within(to_box, p))
+            .filter(|&&p| edges.iter().any(|edge| segment_distance(p, edge[0], edge[1]) <= tolerance))
+            .copied()
+            .take(2)
+            .collect::<Vec<_>>()
+    };
+
+    // A corner shared by both shapes is found from either side; count it once.
+    let mut points = touching(a, b, a_box, b_box);
+    for point in touching(b, a, b_box, a_box) {
+        if !points.contains(&point) {
+            points.push(point);
+        }
+    }
+
+    points.len() >= 2
+}
+
+/// `[min_lon, min_lat, max_lon, max_lat]` of the raw coordinates, grown by
+/// `margin` degrees on every side.
+fn planar_bounds(polygons: &[Polygon], margin: f64) -> Option<[f64; 4]> {
+    let mut points = polygons.iter().flatten().flatten().peekable();
+    points.peek()?;
+
+    Some(points.fold(
+        [f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY],
+        |[min_lon, min_lat, max_lon, max_lat], &[lon, lat]| {
+            [min_lon.min(lon - margin), min_lat.min(lat - margin), max_lon.max(lon + margin), max_lat.max(lat + margin)]
+        },
+    ))
+}
+
+/// Resolution at which country borders are returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GeometryDetail {
+    /// Geometry is omitted.
+    None,
+    Low,
+    Medium,
+    #[default]
+    Full,
+}
+
+impl GeometryDetail {
+    pub fn parse(detail: Option<&str>) -> Result<Self, AppError> {
+        Self::parse_or(detail, Self::default())
+    }
+
+    /// Like `parse`, with the level used when `detail` is absent.
+    pub fn parse_or(detail: Option<&str>, default: Self) -> Result<Self, AppError> {
+        match detail.map(str::trim) {
+            None => Ok(default),
+            Some("none") => Ok(Self::None),
+            Some("low") => Ok(Self::Low),
+            Some("medium") => Ok(Self::Medium),
+            Some("full") => Ok(Self::Full),
+            Some(unknown) => Err(AppError::UnprocessableEntity(json!({
+                "error": format!("Unknown detail '{}'", unknown),
+                "allowed": ["none", "low", "medium", "full"]
+            }))),
+        }
+    }
+
+    /// How the precomputed geometry of this level is derived from the full
+    /// one; `None` for the levels that are not precomputed.
+    pub fn simplification(&self) -> Option<Simplification> {
+        match self {
+            // About 5 km, rounded to roughly 10 m.
+            GeometryDetail::Low => Some(Simplification { tolerance: 0.05, decimals: 4 }),
+            // About 1 km, rounded to roughly 1 m.
+            GeometryDetail::Medium => Some(Simplification { tolerance: 0.01, decimals: 5 }),
+            GeometryDetail::None | GeometryDetail::Full => None,
+        }
+    }
+}
+
+/// Douglas-Peucker tolerance in degrees plus the number of decimals kept in
+/// the simplified coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Simplification {
+    pub tolerance: f64,
+    pub decimals: i32,
+}
+
+/// Simplifies a GeoJSON `Polygon` or `MultiPolygon` with Douglas-Peucker and
+/// rounds its coordinates. Rings that collapse are dropped; if every polygon
+/// collapses, the largest one is kept unsimplified so small countries never
+/// lose their shape.
+pub fn simplify_geo_json(geometry: &serde_json::Value, simplification: Simplification) -> Option<serde_json::Value> {
+    let Simplification { tolerance, decimals } = simplification;
+    let polygons = polygons_from_geo_json(geometry)?;
+    let mut simplified: Vec<Polygon> = polygons
+        .iter()
+        .filter_map(|polygon| {
+            let mut rings = polygon.iter().map(|ring| simplify_ring(ring, tolerance, decimals));
+            let outer = rings.next().filter(|ring| ring.len() >= 4)?;
+
+            Some(std::iter::once(outer).chain(rings.filter(|ring| ring.len() >= 4)).collect())
+        })
+        .collect();
+
+    if simplified.is_empty() {
+        let largest = polygons.into_iter().max_by_key(|polygon| polygon.first().map_or(0, Vec::len))?;
+        simplified.push(largest);
+    }
+
+    let geometry_type = geometry.get("type")?.as_str()?;
+    Some(if geometry_type == "Polygon" && simplified.len() == 1 {
+        json!({ "type": "Polygon", "coordinates": simplified.remove(0) })
+    } else {
+        json!({ "type": "MultiPolygon", "coordinates": simplified })
+    })
+}
+
+fn simplify_ring(ring: &Ring, tolerance: f64, decimals: i32) -> Ring {
+    let factor = 10f64.powi(decimals);
+    let round = |[lon, lat]: [f64; 2]| [(lon * factor).round() / factor, (lat * factor).round() / factor];
+
+    if ring.len() < 4 {
+        return ring.iter().copied().map(round).collect();
+    }
+
+    // A closed ring starts and ends on the same point, so split it at the
+    // point farthest from the start and simplify both halves.
+    let farthest = (1..ring.len() - 1)
+        .max_by(|&a, &b| squared_distance(ring[0], ring[a]).total_cmp(&squared_distance(ring[0], ring[b])))
+        .unwrap_or(1);
+    let mut keep = vec![false; ring.len()];
+    keep[0] = true;
+    keep[farthest] = true;
+    keep[ring.len() - 1] = true;
+    douglas_peucker(ring, 0, farthest, tolerance, &mut keep);
+    douglas_peucker(ring, farthest, ring.len() - 1, tolerance, &mut keep);
+
+    let mut simplified: Ring = ring
+        .iter()
+        .zip(keep)
+        .filter_map(|(point, keep)| keep.then_some(round(*point)))
+        .collect();
+    simplified.dedup();
+
+    simplified
+}
+
+fn douglas_peucker(points: &[[f64; 2]], first: usize, last: usize, tolerance: f64, keep: &mut [bool]) {
+    if last <= first + 1 {
+        return;
+    }
+
+    let (index, distance) = (first + 1..last)
+        .map(|i| (i, segment_distance(points[i], points[first], points[last])))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .unwrap_or((first, 0.0));
+
+    if distance > tolerance {
+        keep[index] = true;
+        douglas_peucker(points, first, index, tolerance, keep);
+        douglas_peucker(points, index, last, tolerance, keep);
+    }
+}
+
+fn squared_distance([ax, ay]: [f64; 2], [bx, by]: [f64; 2]) -> f64 {
+    (bx - ax).powi(2) + (by - ay).powi(2)
+}
+
+/// Planar distance from `p` to the segment `a`-`b`, in degrees.
+fn segment_distance(p: [f64; 2], a: [f64; 2], b: [f64; 2]) -> f64 {
+    let length_squared = squared_distance(a, b);
+    if length_squared == 0.0 {
+        return squared_distance(p, a).sqrt();
+    }
+
+    let t = (((p[0] - a[0]) * (b[0] - a[0]) + (p[1] - a[1]) * (b[1] - a[1])) / length_squared).clamp(0.0, 1.0);
+    squared_distance(p, [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])]).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(min: f64, max: f64) -> Ring {
+        vec![[min, min], [max, min], [max, max], [min, max], [min, min]]
+    }
+
+    #[test]
+    fn bounding_boxes_are_parsed_as_lon_lat_pairs() {
+        let bbox = BoundingBox::parse(" 5.8, 47.2 ,15.1,55.1").unwrap();
+
+        assert_eq!((bbox.min_lon, bbox.min_lat, bbox.max_lon, bbox.max_lat), (5.8, 47.2, 15.1, 55.1));
+        assert!(!bbox.crosses_antimeridian());
+        assert!(bbox.contains(52.5, 13.4));
+        assert!(!bbox.contains(48.9, 2.3));
+    }
+
+    #[test]
+    fn bounding_boxes_may_cross_the_antimeridian() {
+        let bbox = BoundingBox::parse("170,-20,-170,-10").unwrap();
+
+        assert!(bbox.crosses_antimeridian());
+        assert!(bbox.contains(-17.0, 179.0));
+        assert!(bbox.contains(-17.0, -175.0));
+        assert!(!bbox.contains(-17.0, 0.0));
+    }
+
+    #[test]
+    fn malformed_bounding_boxes_are_rejected() {
+        for value in ["", "1,2,3", "1,2,3,4,5", "a,b,c,d", "0,10,10,0", "-181,0,0,10", "0,-91,10,0"] {
+            assert!(matches!(BoundingBox::parse(value), Err(AppError::UnprocessableEntity(_))), "{}", value);
+        }
+    }
+
+    #[test]
+    fn polygon_contains_excludes_holes() {
+        let polygon = vec![square(0.0, 10.0), square(4.0, 6.0)];
+
+        assert!(polygon_contains(&polygon, 2.0, 2.0));
+        assert!(!polygon_contains(&polygon, 5.0, 5.0));
+        assert!(!polygon_contains(&polygon, 20.0, 20.0));
+    }
+
+    #[test]
+    fn polygons_contain_shapes_split_at_the_antimeridian() {
+        let east = vec![vec![[178.0, -18.0], [180.0, -18.0], [180.0, -16.0], [178.0, -16.0], [178.0, -18.0]]];
+        let west = vec![vec![[-180.0, -18.0], [-178.0, -18.0], [-178.0, -16.0], [-180.0, -16.0], [-180.0, -18.0]]];
+        let polygons = vec![east, west];
+
+        assert!(polygons_contain(&polygons, -17.0, 179.5));
+        assert!(polygons_contain(&polygons, -17.0, -179.5));
+        assert!(!polygons_contain(&polygons, -17.0, 0.0));
+        assert!(!polygons_contain(&polygons, -17.0, 177.0));
+    }
+
+    #[test]
+    fn simplify_ring_keeps_endpoints_and_closes_the_ring() {
+        let ring = vec![[0.0, 0.0], [5.0, 0.001], [10.0, 0.0], [10.0, 10.0], [5.0, 10.001], [0.0, 10.0], [0.0, 0.0]];
+        let simplified = simplify_ring(&ring, 0.01, 5);
+
+        assert_eq!(simplified, vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0], [0.0, 0.0]]);
+    }
+
+    #[test]
+    fn simplify_ring_keeps_points_beyond_the_tolerance() {
+        let ring = vec![[0.0, 0.0], [5.0, 1.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0], [0.0, 0.0]];
+        let simplified = simplify_ring(&ring, 0.5, 5);
+
+        assert_eq!(simplified, ring);
+    }
+
+    #[test]
+    fn simplify_geo_json_keeps_the_largest_polygon_when_all_collapse() {
+        let tiny = |offset: f64| vec![vec![
+            [offset, 0.0], [offset + 0.001, 0.0], [offset + 0.001, 0.001], [offset, 0.001], [offset, 0.0],
+        ]];
+        let geometry = json!({ "type": "MultiPolygon", "coordinates": [tiny(0.0), tiny(1.0)] });
+        let simplification = Simplification { tolerance: 1.0, decimals: 1 };
+
+        let simplified = simplify_geo_json(&geometry, simplification).unwrap();
+        let polygons = polygons_from_geo_json(&simplified).unwrap();
+
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0][0].len(), 5);
+        assert_eq!(polygons[0][0].first(), polygons[0][0].last());
+    }
+
+    #[test]
+    fn shape_metrics_wrap_the_box_of_shapes_split_at_the_antimeridian() {
+        let east = vec![vec![[178.0, -18.0], [180.0, -18.0], [180.0, -16.0], [178.0, -16.0], [178.0, -18.0]]];
+        let west = vec![vec![[-180.0, -18.0], [-178.0, -18.0], [-178.0, -16.0], [-180.0, -16.0], [-180.0, -18.0]]];
+        let metrics = shape_metrics(&[east, west]).unwrap();
+
+        assert_eq!(metrics.bounding_box, BoundingBox { min_lat: -18.0, min_lon: 178.0, max_lat: -16.0, max_lon: -178.0 });
+        assert!(metrics.bounding_box.crosses_antimeridian());
+        assert!((metrics.centroid.0 + 17.0).abs() < 1e-9);
+        assert!(metrics.centroid.1.abs() > 179.0);
+    }
+
+    #[test]
+    fn shapes_share_border_needs_more_than_a_corner() {
+        let a = vec![vec![square(0.0, 10.0)]];
+        let beside = vec![vec![vec![[10.0, 0.0], [20.0, 0.0], [20.0, 10.0], [10.0, 10.0], [10.0, 0.0]]]];
+        let corner = vec![vec![square(10.0, 20.0)]];
+        let apart = vec![vec![square(11.0, 20.0)]];
+
+        assert!(shapes_share_border(&a, &beside, 0.01));
+        assert!(!shapes_share_border(&a, &corner, 0.01));
+        assert!(!shapes_share_border(&a, &apart, 0.01));
+    }
+
+    #[test]
+    fn grown_and_split_clamps_and_splits_at_the_antimeridian() {
+        let inland = BoundingBox { min_lat: 10.0, min_lon: 20.0, max_lat: 30.0, max_lon: 40.0 };
+        assert_eq!(
+            inland.grown_and_split(1.0),
+            vec![BoundingBox { min_lat: 9.0, min_lon: 19.0, max_lat: 31.0, max_lon: 41.0 }]
+        );
+
+        let wrapping = BoundingBox { min_lat: -89.5, min_lon: 170.0, max_lat: -10.0, max_lon: -170.0 };
+        assert_eq!(
+            wrapping.grown_and_split(1.0),
+            vec![
+                BoundingBox { min_lat: -90.0, min_lon: 169.0, max_lat: -9.0, max_lon: 180.0 },
+                BoundingBox { min_lat: -90.0, min_lon: -180.0, max_lat: -9.0, max_lon: -169.0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn polygons_from_geo_json_rejects_other_geometries() {
+        assert!(polygons_from_geo_json(&json!({ "type": "Point", "coordinates": [1.0, 2.0] })).is_none());
+        assert!(polygons_from_geo_json(&json!({ "type": "Polygon", "coordinates": [[["a", 0.0]]] })).is_none());
+    }
+}
@@ -0,0 +1,93 @@
+use crate::error::AppError;
+use actix_web::{http::header::ACCEPT, HttpRequest, HttpResponse, HttpResponseBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub const GEO_JSON_CONTENT_TYPE: &str = "application/geo+json";
+
+/// Representation requested by the client for list endpoints that can also
+/// be rendered as GeoJSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseFormat {
+    #[default]
+    Json,
+    GeoJson,
+}
+
+impl ResponseFormat {
+    /// An explicit `format` query parameter (`json` or `geojson`) wins over
+    /// an `Accept: application/geo+json` header.
+    pub fn negotiate(format: Option<&str>, req: &HttpRequest) -> Result<Self, AppError> {
+        match format.map(str::trim) {
+            Some("json") => Ok(Self::Json),
+            Some("geojson") => Ok(Self::GeoJson),
+            Some(unknown) => Err(AppError::UnprocessableEntity(json!({
+                "error": format!("Unknown format '{}'", unknown),
+                "allowed": ["json", "geojson"]
+            }))),
+            None => {
+                let accepts_geo_json = req
+                    .headers()
+                    .get(ACCEPT)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.contains(GEO_JSON_CONTENT_TYPE));
+
+                Ok(if accepts_geo_json { Self::GeoJson } else { Self::Json })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct Feature {
+    #[schema(example = "Feature")]
+    pub r#type: String,
+    pub id: Uuid,
+    /// GeoJSON geometry, `null` when the record has no location.
+    pub geometry: Option<serde_json::Value>,
+    pub properties: serde_json::Value,
+}
+
+impl Feature {
+    pub fn new(id: Uuid, geometry: Option<serde_json::Value>, properties: serde_json::Value) -> Self {
+        Self {
+            r#type: "Feature".to_string(),
+            id,
+            geometry,
+            properties,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct FeatureCollection {
+    #[schema(example = "FeatureCollection")]
+    pub r#type: String,
+    pub features: Vec<Feature>,
+}
+
+impl FeatureCollection {
+    pub fn new(features: Vec<Feature>) -> Self {
+        Self {
+            r#type: "FeatureCollection".to_string(),
+            features,
+        }
+    }
+
+    pub fn into_response(self) -> HttpResponse {
+        self.respond(HttpResponse::Ok())
+    }
+
+    /// Finishes a response prepared by the caller, e.g. with extra headers.
+    pub fn respond(self, mut response: HttpResponseBuilder) -> HttpResponse {
+        response
+            .content_type(GEO_JSON_CONTENT_TYPE)
+            .json(self)
+    }
+}
+
+pub fn point(latitude: f64, longitude: f64) -> serde_json::Value {
+    json!({ "type": "Point", "coordinates": [longitude, latitude] })
+}
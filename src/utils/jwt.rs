@@ -0,0 +1,83 @@
+use crate::app::features::auth::permissions::Role;
+use crate::constants::{auth, env_key};
+use crate::error::AppError;
+use chrono::{Duration, NaiveDateTime, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::env;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenKind {
+    Access,
+    Refresh,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: Uuid,
+    pub role: Role,
+    pub jti: Uuid,
+    pub kind: TokenKind,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+impl Claims {
+    pub fn expires_at(&self) -> NaiveDateTime {
+        chrono::DateTime::from_timestamp(self.exp, 0)
+            .map(|dt| dt.naive_utc())
+            .unwrap_or_default()
+    }
+}
+
+pub struct IssuedToken {
+    pub token: String,
+    pub claims: Claims,
+}
+
+fn secret() -> Result<String, AppError> {
+    env::var(env_key::JWT_SECRET).map_err(|_| {
+        log::error!("{} must be set to issue or verify tokens", env_key::JWT_SECRET);
+        AppError::InternalServerError
+    })
+}
+
+pub fn issue_token(user_id: Uuid, role: Role, kind: TokenKind) -> Result<IssuedToken, AppError> {
+    let now = Utc::now();
+    let ttl = match kind {
+        TokenKind::Access => Duration::minutes(auth::ACCESS_TOKEN_TTL_MINUTES),
+        TokenKind::Refresh => Duration::days(auth::REFRESH_TOKEN_TTL_DAYS),
+    };
+    let claims = Claims {
+        sub: user_id,
+        role,
+        jti: Uuid::new_v4(),
+        kind,
+        iat: now.timestamp(),
+        exp: (now + ttl).timestamp(),
+    };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret()?.as_bytes()),
+    )?;
+
+    Ok(IssuedToken { token, claims })
+}
+
+pub fn verify_token(token: &str, expected_kind: TokenKind) -> Result<Claims, AppError> {
+    let data = decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(secret()?.as_bytes()),
+        &Validation::default(),
+    )?;
+
+    if data.claims.kind != expected_kind {
+        return Err(AppError::Unauthorized(json!({ "error": "Unexpected token type" })));
+    }
+
+    Ok(data.claims)
+}
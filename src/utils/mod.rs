@@ -1,2 +1,10 @@
 pub mod db;
 pub mod di;
+pub mod geo;
+pub mod geojson;
+pub mod jwt;
+pub mod mvt;
+pub mod pagination;
+pub mod search;
+pub mod sorting;
+pub mod topojson;
@@ -0,0 +1,451 @@
+//! Minimal Mapbox Vector Tile (MVT 2.1) encoder: Web Mercator tile math,
+//! polygon clipping and the protobuf wire format of `vector_tile.proto`.
+
+use crate::utils::geo::{BoundingBox, Polygon};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::hash::Hash;
+
+pub const MVT_CONTENT_TYPE: &str = "application/vnd.mapbox-vector-tile";
+
+/// Highest latitude representable in Web Mercator.
+const MAX_MERCATOR_LATITUDE: f64 = 85.051_128_779_806_59;
+
+/// Address of a tile in the XYZ scheme used by web maps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileCoord {
+    pub z: u32,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl TileCoord {
+    /// Whether `x` and `y` lie within the `2^z x 2^z` grid of the zoom level.
+    pub fn is_valid(&self) -> bool {
+        let size = 1u64 << self.z;
+        u64::from(self.x) < size && u64::from(self.y) < size
+    }
+
+    /// Area covered by the tile grown by `buffer` tile widths on every side,
+    /// clamped to the Web Mercator world. Never crosses the antimeridian.
+    pub fn bounding_box(&self, buffer: f64) -> BoundingBox {
+        let size = f64::from(1u32 << self.z);
+        let lon = |x: f64| (x / size * 360.0 - 180.0).clamp(-180.0, 180.0);
+        let lat = |y: f64| {
+            let y = y.clamp(0.0, size);
+            (PI * (1.0 - 2.0 * y / size)).sinh().atan().to_degrees()
+        };
+        let (x, y) = (f64::from(self.x), f64::from(self.y));
+
+        BoundingBox {
+            min_lat: lat(y + 1.0 + buffer),
+            min_lon: lon(x - buffer),
+            max_lat: lat(y - buffer),
+            max_lon: lon(x + 1.0 + buffer),
+        }
+    }
+
+    /// Position of a WGS84 point in the tile coordinate space, where the
+    /// tile spans `0..extent` on both axes with `y` pointing down.
+    pub fn project(&self, lat: f64, lon: f64, extent: u32) -> [f64; 2] {
+        let size = f64::from(1u32 << self.z);
+        let lat = lat.clamp(-MAX_MERCATOR_LATITUDE, MAX_MERCATOR_LATITUDE).to_radians();
+        let world_x = (lon + 180.0) / 360.0 * size;
+        let world_y = (1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / PI) / 2.0 * size;
+        let extent = f64::from(extent);
+
+        [(world_x - f64::from(self.x)) * extent, (world_y - f64::from(self.y)) * extent]
+    }
+}
+
+/// Attribute value of a feature.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    String(String),
+    Double(f64),
+    Int(i64),
+    Bool(bool),
+}
+
+/// A named layer collecting features that share one key/value dictionary.
+pub struct Layer {
+    name: String,
+    extent: u32,
+    keys: Dictionary<String>,
+    /// Values are keyed by their encoded bytes, which also makes doubles hashable.
+    values: Dictionary<Vec<u8>>,
+    features: Vec<Vec<u8>>,
+}
+
+impl Layer {
+    pub fn new(name: &str, extent: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            extent,
+            keys: Dictionary::default(),
+            values: Dictionary::default(),
+            features: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    /// Adds a polygon feature from rings already projected into tile space.
+    /// Rings are clipped to the tile grown by `buffer` units and rounded to
+    /// the integer grid; nothing is added when no ring survives.
+    pub fn add_polygons(&mut self, polygons: &[Polygon], buffer: f64, properties: Vec<(&str, PropertyValue)>) {
+        let geometry = encode_polygons(polygons, -buffer, f64::from(self.extent) + buffer);
+        if geometry.is_empty() {
+            return;
+        }
+
+        self.add_feature(GEOM_TYPE_POLYGON, geometry, properties);
+    }
+
+    /// Adds a point feature at a position in tile space; points outside the
+    /// tile grown by `buffer` units are skipped.
+    pub fn add_point(&mut self, [x, y]: [f64; 2], buffer: f64, properties: Vec<(&str, PropertyValue)>) {
+        let max = f64::from(self.extent) + buffer;
+        if x < -buffer || y < -buffer || x > max || y > max {
+            return;
+        }
+
+        let geometry = vec![command(COMMAND_MOVE_TO, 1), zigzag(x.round() as i64), zigzag(y.round() as i64)];
+        self.add_feature(GEOM_TYPE_POINT, geometry, properties);
+    }
+
+    fn add_feature(&mut self, geom_type: u32, geometry: Vec<u32>, properties: Vec<(&str, PropertyValue)>) {
+        let mut tags = Vec::with_capacity(properties.len() * 2);
+        for (key, value) in properties {
+            tags.push(self.keys.index_of(key.to_string()));
+            tags.push(self.values.index_of(encode_value(&value)));
+        }
+
+        let mut feature = Vec::new();
+        write_packed(&mut feature, 2, &tags);
+        write_varint_field(&mut feature, 3, u64::from(geom_type));
+        write_packed(&mut feature, 4, &geometry);
+        self.features.push(feature);
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut layer = Vec::new();
+        write_varint_field(&mut layer, 15, 2);
+        write_bytes_field(&mut layer, 1, self.name.as_bytes());
+        for feature in &self.features {
+            write_bytes_field(&mut layer, 2, feature);
+        }
+        for key in &self.keys.items {
+            write_bytes_field(&mut layer, 3, key.as_bytes());
+        }
+        for value in &self.values.items {
+            write_bytes_field(&mut layer, 4, value);
+        }
+        write_varint_field(&mut layer, 5, u64::from(self.extent));
+
+        layer
+    }
+}
+
+/// Encodes the non-empty layers into a tile.
+pub fn encode_tile(layers: &[Layer]) -> Vec<u8> {
+    let mut tile = Vec::new();
+    for layer in layers.iter().filter(|layer| !layer.is_empty()) {
+        write_bytes_field(&mut tile, 3, &layer.encode());
+    }
+
+    tile
+}
+
+const GEOM_TYPE_POINT: u32 = 1;
+const GEOM_TYPE_POLYGON: u32 = 3;
+
+const COMMAND_MOVE_TO: u32 = 1;
+const COMMAND_LINE_TO: u32 = 2;
+const COMMAND_CLOSE_PATH: u32 = 7;
+
+/// Distinct keys or values of a layer in insertion order, with their
+/// positions indexed so tagging a feature does not scan the list.
+struct Dictionary<T> {
+    items: Vec<T>,
+    indices: HashMap<T, u32>,
+}
+
+impl<T> Default for Dictionary<T> {
+    fn default() -> Self {
+        Self { items: Vec::new(), indices: HashMap::new() }
+    }
+}
+
+impl<T: Hash + Eq + Clone> Dictionary<T> {
+    fn index_of(&mut self, item: T) -> u32 {
+        if let Some(&index) = self.indices.get(&item) {
+            return index;
+        }
+
+        let index = self.items.len() as u32;
+        self.items.push(item.clone());
+        self.indices.insert(item, index);
+        index
+    }
+}
+
+/// Clips, quantizes and orients the rings, then encodes them as geometry
+/// commands. Exterior rings get a positive and holes a negative area in the
+/// y-down tile space, as the specification requires; holes whose exterior
+/// ring vanished are dropped along with it.
+fn encode_polygons(polygons: &[Polygon], min: f64, max: f64) -> Vec<u32> {
+    let mut geometry = Vec::new();
+    let mut cursor = (0i64, 0i64);
+
+    for polygon in polygons {
+        for (index, ring) in polygon.iter().enumerate() {
+            let ring = quantize(&clip_ring(ring, min, max));
+            let area = ring_area(&ring);
+            if ring.len() < 3 || area == 0 {
+                if index == 0 {
+                    break;
+                }
+                continue;
+            }
+
+            let exterior = index == 0;
+            let ring: Vec<(i64, i64)> = if (area > 0) == exterior { ring } else { ring.into_iter().rev().collect() };
+
+            geometry.push(command(COMMAND_MOVE_TO, 1));
+            push_delta(&mut geometry, &mut cursor, ring[0]);
+            geometry.push(command(COMMAND_LINE_TO, ring.len() as u32 - 1));
+            for &point in &ring[1..] {
+                push_delta(&mut geometry, &mut cursor, point);
+            }
+            geometry.push(command(COMMAND_CLOSE_PATH, 1));
+        }
+    }
+
+    geometry
+}
+
+/// Sutherland-Hodgman clipping of a ring against the square `min..max`.
+fn clip_ring(ring: &[[f64; 2]], min: f64, max: f64) -> Vec<[f64; 2]> {
+    let edges: [(usize, f64, bool); 4] = [(0, min, true), (0, max, false), (1, min, true), (1, max, false)];
+    let mut points: Vec<[f64; 2]> = ring.to_vec();
+
+    for (axis, bound, keep_above) in edges {
+        if points.is_empty() {
+            break;
+        }
+
+        let inside = |p: &[f64; 2]| if keep_above { p[axis] >= bound } else { p[axis] <= bound };
+        let intersect = |a: [f64; 2], b: [f64; 2]| {
+            let t = (bound - a[axis]) / (b[axis] - a[axis]);
+            let mut p = [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])];
+            p[axis] = bound;
+            p
+        };
+
+        let mut clipped = Vec::with_capacity(points.len());
+        let mut previous = points[points.len() - 1];
+        for &current in &points {
+            match (inside(&previous), inside(&current)) {
+                (true, true) => clipped.push(current),
+                (true, false) => clipped.push(intersect(previous, current)),
+                (false, true) => {
+                    clipped.push(intersect(previous, current));
+                    clipped.push(current);
+                }
+                (false, false) => {}
+            }
+            previous = current;
+        }
+        points = clipped;
+    }
+
+    points
+}
+
+/// Rounds to the integer grid and drops repeated points, including the
+/// closing position, which `ClosePath` implies.
+fn quantize(ring: &[[f64; 2]]) -> Vec<(i64, i64)> {
+    let mut points: Vec<(i64, i64)> = ring.iter().map(|p| (p[0].round() as i64, p[1].round() as i64)).collect();
+    points.dedup();
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+
+    points
+}
+
+/// Twice the signed area of a ring, positive for clockwise rings in y-down space.
+fn ring_area(ring: &[(i64, i64)]) -> i64 {
+    (0..ring.len())
+        .map(|i| {
+            let (x1, y1) = ring[i];
+            let (x2, y2) = ring[(i + 1) % ring.len()];
+            x1 * y2 - x2 * y1
+        })
+        .sum()
+}
+
+fn push_delta(geometry: &mut Vec<u32>, cursor: &mut (i64, i64), (x, y): (i64, i64)) {
+    geometry.push(zigzag(x - cursor.0));
+    geometry.push(zigzag(y - cursor.1));
+    *cursor = (x, y);
+}
+
+fn command(id: u32, count: u32) -> u32 {
+    (id & 0x7) | (count << 3)
+}
+
+fn zigzag(value: i64) -> u32 {
+    ((value << 1) ^ (value >> 63)) as u32
+}
+
+fn encode_value(value: &PropertyValue) -> Vec<u8> {
+    let mut buf = Vec::new();
+    match value {
+        PropertyValue::String(s) => write_bytes_field(&mut buf, 1, s.as_bytes()),
+        PropertyValue::Double(d) => {
+            write_varint(&mut buf, (3 << 3) | 1);
+            buf.extend_from_slice(&d.to_le_bytes());
+        }
+        PropertyValue::Int(i) => write_varint_field(&mut buf, 4, *i as u64),
+        PropertyValue::Bool(b) => write_varint_field(&mut buf, 7, u64::from(*b)),
+    }
+
+    buf
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_varint_field(buf: &mut Vec<u8>, field: u32, value: u64) {
+    write_varint(buf, u64::from(field << 3));
+    write_varint(buf, value);
+}
+
+fn write_bytes_field(buf: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    write_varint(buf, u64::from(field << 3 | 2));
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn write_packed(buf: &mut Vec<u8>, field: u32, values: &[u32]) {
+    let mut packed = Vec::new();
+    for &value in values {
+        write_varint(&mut packed, u64::from(value));
+    }
+    write_bytes_field(buf, field, &packed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(min: f64, max: f64) -> Vec<[f64; 2]> {
+        vec![[min, min], [max, min], [max, max], [min, max], [min, min]]
+    }
+
+    /// Absolute ring positions and close commands decoded from geometry commands.
+    fn decode(geometry: &[u32]) -> Vec<Vec<(i64, i64)>> {
+        let unzigzag = |value: u32| i64::from(value >> 1) ^ -i64::from(value & 1);
+        let (mut rings, mut cursor, mut i) = (Vec::new(), (0i64, 0i64), 0);
+        while i < geometry.len() {
+            let (id, count) = (geometry[i] & 0x7, geometry[i] >> 3);
+            i += 1;
+            if id == COMMAND_MOVE_TO {
+                rings.push(Vec::new());
+            }
+            if id == COMMAND_CLOSE_PATH {
+                continue;
+            }
+            for _ in 0..count {
+                cursor = (cursor.0 + unzigzag(geometry[i]), cursor.1 + unzigzag(geometry[i + 1]));
+                rings.last_mut().unwrap().push(cursor);
+                i += 2;
+            }
+        }
+
+        rings
+    }
+
+    #[test]
+    fn zigzag_interleaves_signs() {
+        assert_eq!(zigzag(0), 0);
+        assert_eq!(zigzag(-1), 1);
+        assert_eq!(zigzag(1), 2);
+        assert_eq!(zigzag(-2), 3);
+        assert_eq!(zigzag(2147483647), 4294967294);
+    }
+
+    #[test]
+    fn varints_use_seven_bits_per_byte() {
+        let mut buf = Vec::new();
+        write_varint(&mut buf, 1);
+        write_varint(&mut buf, 300);
+        write_varint(&mut buf, 16384);
+        assert_eq!(buf, vec![0x01, 0xac, 0x02, 0x80, 0x80, 0x01]);
+    }
+
+    #[test]
+    fn fields_carry_their_wire_type() {
+        let mut buf = Vec::new();
+        write_varint_field(&mut buf, 15, 2);
+        write_bytes_field(&mut buf, 1, b"ab");
+        write_packed(&mut buf, 4, &[9, 300]);
+        assert_eq!(buf, vec![0x78, 0x02, 0x0a, 0x02, b'a', b'b', 0x22, 0x03, 0x09, 0xac, 0x02]);
+    }
+
+    #[test]
+    fn exterior_rings_are_clockwise_and_holes_counter_clockwise() {
+        // Both rings are given with the same winding, once each way round.
+        for reversed in [false, true] {
+            let orient = |mut ring: Vec<[f64; 2]>| {
+                if reversed {
+                    ring.reverse();
+                }
+                ring
+            };
+            let polygon = vec![orient(square(10.0, 100.0)), orient(square(40.0, 60.0))];
+            let rings = decode(&encode_polygons(&[polygon], -64.0, 4160.0));
+
+            assert_eq!(rings.len(), 2);
+            assert!(ring_area(&rings[0]) > 0);
+            assert!(ring_area(&rings[1]) < 0);
+        }
+    }
+
+    #[test]
+    fn rings_are_clipped_to_the_buffered_tile() {
+        let rings = decode(&encode_polygons(&[vec![square(-500.0, 100.0)]], -64.0, 4160.0));
+
+        assert_eq!(rings.len(), 1);
+        assert!(rings[0].iter().all(|&(x, y)| (-64..=100).contains(&x) && (-64..=100).contains(&y)));
+        assert_eq!(ring_area(&rings[0]), 164 * 164 * 2);
+    }
+
+    #[test]
+    fn rings_outside_the_tile_drop_their_holes() {
+        let polygon = vec![square(5000.0, 6000.0), square(5400.0, 5600.0)];
+        assert!(encode_polygons(&[polygon], -64.0, 4160.0).is_empty());
+    }
+
+    #[test]
+    fn layer_dictionaries_hold_each_key_and_value_once() {
+        let mut layer = Layer::new("points", 4096);
+        for name in ["a", "b", "a"] {
+            layer.add_point([1.0, 2.0], 0.0, vec![("name", PropertyValue::String(name.to_string())), ("rank", PropertyValue::Double(1.5))]);
+        }
+        layer.add_point([5000.0, 2.0], 0.0, vec![("name", PropertyValue::String("c".to_string()))]);
+
+        assert_eq!(layer.features.len(), 3);
+        assert_eq!(layer.keys.items, vec!["name".to_string(), "rank".to_string()]);
+        assert_eq!(layer.values.items.len(), 3);
+    }
+}
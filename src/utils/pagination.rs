@@ -0,0 +1,316 @@
+use crate::constants::pagination::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::error::AppError;
+use actix_web::{http::header::LINK, HttpRequest, HttpResponse, HttpResponseBuilder};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+/// Format of timestamp sort keys in cursors: microsecond precision, as
+/// stored by PostgreSQL, so cursors round-trip exactly.
+pub const CURSOR_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.6f";
+
+/// Position after the last row of a page in the stable order of a listing:
+/// the sort key of that row, rendered as text, and its id as tie-breaker.
+/// Clients only ever see it base64 encoded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    #[serde(rename = "k")]
+    pub key: Option<String>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn new(key: Option<String>, id: Uuid) -> Self {
+        Self { key, id }
+    }
+
+    pub fn encode(&self) -> String {
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    pub fn decode(value: &str) -> Result<Self, AppError> {
+        URL_SAFE_NO_PAD
+            .decode(value.trim())
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(invalid_cursor)
+    }
+
+    /// The key parsed by the listing the cursor was issued for.
+    pub fn parse_key<K>(&self, parse: impl FnOnce(&str) -> Option<K>) -> Result<K, AppError> {
+        self.key.as_deref().and_then(parse).ok_or_else(invalid_cursor)
+    }
+}
+
+fn invalid_cursor() -> AppError {
+    AppError::UnprocessableEntity(json!({ "error": "Invalid cursor" }))
+}
+
+/// Which page of a listing to load. Offset paging is kept for existing
+/// clients; keyset paging continues after a cursor and stays fast and
+/// consistent however deep the client pages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageRequest {
+    Offset { limit: i64, offset: i64 },
+    Cursor { limit: i64, after: Cursor },
+}
+
+impl PageRequest {
+    /// Validates the `limit`, `offset` and `cursor` query parameters; a
+    /// cursor selects keyset paging and cannot be combined with an offset.
+    pub fn parse(limit: Option<i64>, offset: Option<i64>, cursor: Option<&str>) -> Result<Self, AppError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT);
+        if !(1..=MAX_LIMIT).contains(&limit) {
+            return Err(AppError::UnprocessableEntity(json!({
+                "error": format!("limit must be between 1 and {}", MAX_LIMIT)
+            })));
+        }
+
+        match (offset, cursor) {
+            (Some(_), Some(_)) => Err(AppError::UnprocessableEntity(json!({
+                "error": "cursor and offset cannot be combined"
+            }))),
+            (_, Some(cursor)) => Ok(Self::Cursor { limit, after: Cursor::decode(cursor)? }),
+            (offset, None) => {
+                let offset = offset.unwrap_or(0);
+                if offset < 0 {
+                    return Err(AppError::UnprocessableEntity(json!({ "error": "offset must not be negative" })));
+                }
+
+                Ok(Self::Offset { limit, offset })
+            }
+        }
+    }
+
+    pub fn limit(&self) -> i64 {
+        match self {
+            Self::Offset { limit, .. } | Self::Cursor { limit, .. } => *limit,
+        }
+    }
+
+    /// Rows to load: keyset pages load one extra row to tell whether
+    /// another page follows.
+    pub fn fetch_limit(&self) -> i64 {
+        match self {
+            Self::Offset { limit, .. } => *limit,
+            Self::Cursor { limit, .. } => limit + 1,
+        }
+    }
+
+    pub fn offset(&self) -> Option<i64> {
+        match self {
+            Self::Offset { offset, .. } => Some(*offset),
+            Self::Cursor { .. } => None,
+        }
+    }
+
+    pub fn cursor(&self) -> Option<&Cursor> {
+        match self {
+            Self::Offset { .. } => None,
+            Self::Cursor { after, .. } => Some(after),
+        }
+    }
+}
+
+/// One page of a filtered listing together with the number of rows matching
+/// the filters across all pages.
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub limit: i64,
+    /// `None` for keyset pages.
+    pub offset: Option<i64>,
+    pub has_more: bool,
+    /// Continues after the last item, set whenever another page follows.
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Builds the page from rows loaded with `request.fetch_limit()`;
+    /// `cursor_of` gives the position of a row in the listing's order.
+    pub fn new(mut items: Vec<T>, total: i64, request: &PageRequest, cursor_of: impl Fn(&T) -> Cursor) -> Self {
+        let limit = request.limit();
+        let has_more = match request {
+            PageRequest::Offset { offset, .. } => offset + (items.len() as i64) < total,
+            PageRequest::Cursor { .. } => items.len() as i64 > limit,
+        };
+        items.truncate(limit as usize);
+        let next_cursor = items
+            .last()
+            .filter(|_| has_more)
+            .map(|item| cursor_of(item).encode());
+
+        Self {
+            items,
+            total,
+            limit,
+            offset: request.offset(),
+            has_more,
+            next_cursor,
+        }
+    }
+
+    /// Drops `next_cursor`, for pages in an order cursors cannot continue.
+    pub fn without_cursor(mut self) -> Self {
+        self.next_cursor = None;
+        self
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            limit: self.limit,
+            offset: self.offset,
+            has_more: self.has_more,
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+/// Request path and query without the paging parameters, from which the
+/// RFC 8288 `Link` header of a page is built.
+#[derive(Debug, Clone, Default)]
+pub struct PageLinks {
+    path: String,
+    query: Vec<String>,
+}
+
+impl PageLinks {
+    pub fn from_request(req: &HttpRequest) -> Self {
+        let query = req
+            .query_string()
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .filter(|pair| {
+                let key = pair.split('=').next().unwrap_or_default();
+                !matches!(key, "limit" | "offset" | "cursor")
+            })
+            .map(str::to_string)
+            .collect();
+
+        Self {
+            path: req.path().to_string(),
+            query,
+        }
+    }
+
+    /// `first`, `prev`, `next` and `last` links as relative references;
+    /// `prev` and `next` are left out on the first and last page. Keyset
+    /// pages only link to the first and the next page.
+    pub fn header_value<T>(&self, page: &Page<T>) -> Option<String> {
+        let limit = page.limit;
+        let Some(offset) = page.offset else {
+            let mut links = vec![self.link(&[format!("limit={}", limit)], "first")];
+            if let Some(cursor) = &page.next_cursor {
+                links.push(self.link(&[format!("limit={}", limit), format!("cursor={}", cursor)], "next"));
+            }
+
+            return Some(links.join(", "));
+        };
+
+        let offset_link = |offset: i64, rel: &str| self.link(&[format!("limit={}", limit), format!("offset={}", offset)], rel);
+        let last_offset = ((page.total - 1).max(0) / limit) * limit;
+        let mut links = vec![offset_link(0, "first")];
+        if offset > 0 {
+            links.push(offset_link((offset - limit).max(0), "prev"));
+        }
+        if page.has_more {
+            links.push(offset_link(offset + limit, "next"));
+        }
+        links.push(offset_link(last_offset, "last"));
+
+        Some(links.join(", "))
+    }
+
+    /// `200 OK` response carrying the `Link` header of the page.
+    pub fn ok<T>(&self, page: &Page<T>) -> HttpResponseBuilder {
+        let mut response = HttpResponse::Ok();
+        if let Some(value) = self.header_value(page) {
+            response.insert_header((LINK, value));
+        }
+
+        response
+    }
+
+    fn link(&self, paging: &[String], rel: &str) -> String {
+        let query: Vec<&str> = self.query.iter().chain(paging).map(String::as_str).collect();
+
+        format!("<{}?{}>; rel=\"{}\"", self.path, query.join("&"), rel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(n: u128) -> Uuid {
+        Uuid::from_u128(n)
+    }
+
+    #[test]
+    fn cursors_round_trip() {
+        for cursor in [Cursor::new(Some("Côte d'Ivoire".to_string()), id(7)), Cursor::new(None, id(8))] {
+            let encoded = cursor.encode();
+
+            assert!(encoded.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+            assert_eq!(Cursor::decode(&encoded).unwrap(), cursor);
+        }
+    }
+
+    #[test]
+    fn malformed_cursors_are_rejected() {
+        let encode = |text: &str| URL_SAFE_NO_PAD.encode(text);
+
+        for value in [
+            "not base64!".to_string(),
+            encode("not json"),
+            encode(r#"{"k":"a"}"#),
+            encode(r#"{"k":"a","id":"not-a-uuid"}"#),
+        ] {
+            assert!(matches!(Cursor::decode(&value), Err(AppError::UnprocessableEntity(_))), "{}", value);
+        }
+    }
+
+    #[test]
+    fn cursor_keys_the_listing_cannot_parse_are_rejected() {
+        let cursor = Cursor::new(Some("abc".to_string()), id(1));
+
+        assert!(cursor.parse_key(|key| key.parse::<i64>().ok()).is_err());
+        assert!(Cursor::new(None, id(1)).parse_key(|key| Some(key.to_string())).is_err());
+        assert_eq!(cursor.parse_key(|key| Some(key.len())).unwrap(), 3);
+    }
+
+    #[test]
+    fn page_requests_are_validated() {
+        assert_eq!(PageRequest::parse(None, None, None).unwrap(), PageRequest::Offset { limit: DEFAULT_LIMIT, offset: 0 });
+        assert!(PageRequest::parse(Some(0), None, None).is_err());
+        assert!(PageRequest::parse(Some(MAX_LIMIT + 1), None, None).is_err());
+        assert!(PageRequest::parse(None, Some(-1), None).is_err());
+
+        let cursor = Cursor::new(None, id(3)).encode();
+        assert!(PageRequest::parse(None, Some(0), Some(&cursor)).is_err());
+        assert_eq!(
+            PageRequest::parse(Some(5), None, Some(&cursor)).unwrap(),
+            PageRequest::Cursor { limit: 5, after: Cursor::new(None, id(3)) }
+        );
+    }
+
+    #[test]
+    fn keyset_pages_continue_after_their_last_item() {
+        let request = PageRequest::Cursor { limit: 2, after: Cursor::new(None, id(0)) };
+        let cursor_of = |n: &u128| Cursor::new(None, id(*n));
+
+        let page = Page::new(vec![1, 2, 3], 10, &request, cursor_of);
+        assert_eq!(page.items, vec![1, 2]);
+        assert!(page.has_more);
+        assert_eq!(Cursor::decode(page.next_cursor.as_deref().unwrap()).unwrap(), cursor_of(&2));
+
+        let last = Page::new(vec![9, 10], 10, &request, cursor_of);
+        assert!(!last.has_more);
+        assert_eq!(last.next_cursor, None);
+    }
+}
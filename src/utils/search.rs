@@ -0,0 +1,73 @@
+use crate::error::AppError;
+use serde_json::json;
+
+pub const MAX_QUERY_LENGTH: usize = 200;
+
+/// Text search configuration used by the `search_vector` columns.
+pub const TEXT_SEARCH_CONFIG: &str = "simple";
+
+/// `ts_headline` options: matches are wrapped in `<mark>` and the snippet
+/// is cut to a few short fragments.
+pub const HEADLINE_OPTIONS: &str = "StartSel=<mark>, StopSel=</mark>, MaxWords=20, MinWords=5, MaxFragments=2, FragmentDelimiter=\" … \"";
+
+/// SQL expression HTML-escaping the text expression `expr`. Snippets are
+/// HTML, so stored text is escaped before `ts_headline` adds its `<mark>`
+/// tags and cannot inject markup of its own.
+pub fn html_escaped_sql(expr: &str) -> String {
+    format!(
+        "replace(replace(replace(replace(replace({}, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '\"', '&quot;'), '''', '&#39;')",
+        expr
+    )
+}
+
+/// How well a row matched a search: full-text rank plus name similarity,
+/// and a snippet of the row with the matched words highlighted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchMatch {
+    pub rank: f32,
+    /// HTML-escaped text with the matched words wrapped in `<mark>`.
+    pub snippet: String,
+}
+
+/// A free-text query as typed by the user, prepared for PostgreSQL
+/// full-text search and trigram similarity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    /// Trimmed query, compared by trigram word similarity.
+    pub text: String,
+    /// `to_tsquery` input requiring every word as a prefix, so results
+    /// narrow down while typing; `None` when the query has no words.
+    pub all_words: Option<String>,
+    /// `to_tsquery` input matching any word, used to highlight snippets of
+    /// rows that only matched by similarity.
+    pub any_word: Option<String>,
+}
+
+impl SearchQuery {
+    /// A blank `q` parameter is treated as absent.
+    pub fn parse(q: Option<&str>) -> Result<Option<Self>, AppError> {
+        let Some(text) = q.map(str::trim).filter(|text| !text.is_empty()) else {
+            return Ok(None);
+        };
+        if text.chars().count() > MAX_QUERY_LENGTH {
+            return Err(AppError::UnprocessableEntity(json!({
+                "error": format!("q must be at most {} characters", MAX_QUERY_LENGTH)
+            })));
+        }
+
+        // Only letters and digits reach to_tsquery, whose operators would
+        // otherwise make arbitrary input a syntax error.
+        let words: Vec<String> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| format!("{}:*", word.to_lowercase()))
+            .collect();
+        let join = |operator: &str| (!words.is_empty()).then(|| words.join(operator));
+
+        Ok(Some(Self {
+            text: text.to_string(),
+            all_words: join(" & "),
+            any_word: join(" | "),
+        }))
+    }
+}
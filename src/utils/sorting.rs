@@ -0,0 +1,109 @@
+use crate::error::AppError;
+use crate::utils::pagination::PageRequest;
+use serde_json::json;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+/// Column a listing can be ordered by, named as in the `sort` parameter.
+pub trait SortField: Copy + PartialEq + 'static {
+    const ALLOWED: &'static [(&'static str, Self)];
+}
+
+/// Order requested with a `sort` parameter such as `name,-created_at`: a
+/// leading `-` sorts descending. Empty means the listing's default order,
+/// the only one cursors are issued for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sort<F> {
+    pub fields: Vec<(F, Direction)>,
+}
+
+impl<F: SortField> Sort<F> {
+    pub fn parse(value: Option<&str>) -> Result<Self, AppError> {
+        let mut fields: Vec<(F, Direction)> = Vec::new();
+        for item in value.unwrap_or_default().split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (name, direction) = match item.strip_prefix('-') {
+                Some(name) => (name, Direction::Desc),
+                None => (item, Direction::Asc),
+            };
+            let Some(&(_, field)) = F::ALLOWED.iter().find(|(allowed, _)| *allowed == name) else {
+                return Err(AppError::UnprocessableEntity(json!({
+                    "error": format!("Unknown sort field '{}'", name),
+                    "allowed": F::ALLOWED.iter().map(|(allowed, _)| *allowed).collect::<Vec<_>>()
+                })));
+            };
+            if fields.iter().any(|(existing, _)| *existing == field) {
+                return Err(AppError::UnprocessableEntity(json!({
+                    "error": format!("Sort field '{}' is given more than once", name)
+                })));
+            }
+            fields.push((field, direction));
+        }
+
+        Ok(Self { fields })
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Cursors encode a position in the default order only.
+    pub fn check_page(&self, page: &PageRequest) -> Result<(), AppError> {
+        if page.cursor().is_some() && !self.is_default() {
+            return Err(AppError::UnprocessableEntity(json!({
+                "error": "cursor cannot be combined with sort; use offset paging for custom orders"
+            })));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::pagination::Cursor;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Field {
+        Name,
+        CreatedAt,
+    }
+
+    impl SortField for Field {
+        const ALLOWED: &'static [(&'static str, Self)] = &[("name", Field::Name), ("created_at", Field::CreatedAt)];
+    }
+
+    #[test]
+    fn sort_fields_keep_their_order_and_direction() {
+        let sort = Sort::<Field>::parse(Some(" -created_at, name ,")).unwrap();
+
+        assert_eq!(sort.fields, vec![(Field::CreatedAt, Direction::Desc), (Field::Name, Direction::Asc)]);
+        assert!(Sort::<Field>::parse(None).unwrap().is_default());
+        assert!(Sort::<Field>::parse(Some("")).unwrap().is_default());
+    }
+
+    #[test]
+    fn sort_fields_outside_the_whitelist_are_rejected() {
+        for value in ["email", "-name,password", "name,-name", "--name"] {
+            assert!(matches!(Sort::<Field>::parse(Some(value)), Err(AppError::UnprocessableEntity(_))), "{}", value);
+        }
+    }
+
+    #[test]
+    fn cursors_are_only_allowed_in_the_default_order() {
+        let cursor = Cursor::new(None, Uuid::from_u128(1)).encode();
+        let keyset = PageRequest::parse(None, None, Some(&cursor)).unwrap();
+        let offset = PageRequest::parse(None, Some(10), None).unwrap();
+        let sorted = Sort::<Field>::parse(Some("name")).unwrap();
+        let default = Sort::<Field>::parse(None).unwrap();
+
+        assert!(default.check_page(&keyset).is_ok());
+        assert!(sorted.check_page(&offset).is_ok());
+        assert!(matches!(sorted.check_page(&keyset), Err(AppError::UnprocessableEntity(_))));
+    }
+}
@@ -0,0 +1,316 @@
+//! TopoJSON encoding of polygon layers: borders shared by neighbouring
+//! shapes are stored once as arcs and referenced from both sides.
+
+use crate::error::AppError;
+use crate::utils::geo::{polygons_from_geo_json, Polygon};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+
+pub const DEFAULT_QUANTIZATION: u32 = 10_000;
+pub const MAX_QUANTIZATION: u32 = 1_000_000;
+
+/// A shape to be added to the topology.
+pub struct TopologyFeature {
+    pub id: Option<String>,
+    pub properties: Value,
+    /// GeoJSON `Polygon` or `MultiPolygon`; other geometries are skipped.
+    pub geometry: Value,
+}
+
+/// Checks the number of grid steps per axis requested by a client.
+pub fn validate_quantization(quantization: Option<u32>) -> Result<u32, AppError> {
+    let quantization = quantization.unwrap_or(DEFAULT_QUANTIZATION);
+    if !(2..=MAX_QUANTIZATION).contains(&quantization) {
+        return Err(AppError::UnprocessableEntity(json!({
+            "error": format!("quantization must be between 2 and {}", MAX_QUANTIZATION)
+        })));
+    }
+
+    Ok(quantization)
+}
+
+type Point = (i64, i64);
+/// Rings of a polygon on the quantization grid.
+type GridPolygon = Vec<Vec<Point>>;
+
+/// Builds a `Topology` with the features as one `GeometryCollection` object
+/// named `layer`. Coordinates are snapped to a `quantization` x
+/// `quantization` grid over the bounding box of all shapes, which is also
+/// what makes borders traced separately by two shapes line up. Arcs are
+/// delta-encoded as the specification describes for quantized topologies.
+pub fn build_topology(layer: &str, features: &[TopologyFeature], quantization: u32) -> Value {
+    let shapes: Vec<(&TopologyFeature, bool, Vec<Polygon>)> = features
+        .iter()
+        .filter_map(|feature| {
+            let polygons = polygons_from_geo_json(&feature.geometry)?;
+            let multi = feature.geometry.get("type").and_then(Value::as_str) != Some("Polygon");
+            Some((feature, multi, polygons))
+        })
+        .collect();
+
+    let (mut x0, mut y0, mut x1, mut y1) = (f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for &[x, y] in shapes.iter().flat_map(|(_, _, polygons)| polygons.iter().flatten().flatten()) {
+        (x0, y0, x1, y1) = (x0.min(x), y0.min(y), x1.max(x), y1.max(y));
+    }
+    if !x0.is_finite() {
+        (x0, y0, x1, y1) = (0.0, 0.0, 0.0, 0.0);
+    }
+
+    let steps = f64::from(quantization - 1);
+    let kx = if x1 > x0 { (x1 - x0) / steps } else { 1.0 };
+    let ky = if y1 > y0 { (y1 - y0) / steps } else { 1.0 };
+    let quantize = |[x, y]: [f64; 2]| -> Point { (((x - x0) / kx).round() as i64, ((y - y0) / ky).round() as i64) };
+
+    // Quantized rings without their closing point, per polygon and shape.
+    // Rings that collapse to fewer than three points are dropped, and so is
+    // a polygon whose exterior ring collapses.
+    let quantized: Vec<Vec<GridPolygon>> = shapes
+        .iter()
+        .map(|(_, _, polygons)| {
+            polygons
+                .iter()
+                .filter_map(|polygon| {
+                    let rings: GridPolygon = polygon.iter().map(|ring| open_ring(ring.iter().copied().map(quantize))).collect();
+                    let exterior_kept = rings.first().is_some_and(|ring| ring.len() >= 3);
+                    exterior_kept.then(|| rings.into_iter().filter(|ring| ring.len() >= 3).collect())
+                })
+                .collect()
+        })
+        .collect();
+
+    let junctions = find_junctions(quantized.iter().flatten().flatten());
+    let mut arcs = ArcIndex::default();
+    let mut geometries = Vec::new();
+
+    for ((feature, multi, _), polygons) in shapes.iter().zip(&quantized) {
+        if polygons.is_empty() {
+            continue;
+        }
+
+        let polygon_arcs: Vec<Vec<Vec<i64>>> = polygons
+            .iter()
+            .map(|polygon| polygon.iter().map(|ring| cut_ring(ring, &junctions).into_iter().map(|arc| arcs.index(arc)).collect()).collect())
+            .collect();
+
+        let mut geometry = if !multi && polygon_arcs.len() == 1 {
+            json!({ "type": "Polygon", "arcs": polygon_arcs[0] })
+        } else {
+            json!({ "type": "MultiPolygon", "arcs": polygon_arcs })
+        };
+        if let Some(id) = &feature.id {
+            geometry["id"] = json!(id);
+        }
+        geometry["properties"] = feature.properties.clone();
+        geometries.push(geometry);
+    }
+
+    let encoded_arcs: Vec<Vec<[i64; 2]>> = arcs
+        .arcs
+        .iter()
+        .map(|arc| {
+            let mut previous = (0, 0);
+            arc.iter()
+                .map(|&(x, y)| {
+                    let delta = [x - previous.0, y - previous.1];
+                    previous = (x, y);
+                    delta
+                })
+                .collect()
+        })
+        .collect();
+
+    json!({
+        "type": "Topology",
+        "bbox": [x0, y0, x1, y1],
+        "transform": {
+            "scale": [kx, ky],
+            "translate": [x0, y0]
+        },
+        "objects": {
+            layer: {
+                "type": "GeometryCollection",
+                "geometries": geometries
+            }
+        },
+        "arcs": encoded_arcs
+    })
+}
+
+/// Removes repeated points and the closing position of a ring.
+fn open_ring(points: impl Iterator<Item = Point>) -> Vec<Point> {
+    let mut ring: Vec<Point> = points.collect();
+    ring.dedup();
+    while ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+
+    ring
+}
+
+/// Points where rings stop running along each other: a point is a junction
+/// when two of its occurrences have different neighbours.
+fn find_junctions<'a>(rings: impl Iterator<Item = &'a Vec<Point>>) -> HashSet<Point> {
+    let mut neighbours: HashMap<Point, (Point, Point)> = HashMap::new();
+    let mut junctions: HashSet<Point> = HashSet::new();
+
+    for ring in rings {
+        let n = ring.len();
+        for i in 0..n {
+            let (point, previous, next) = (ring[i], ring[(i + n - 1) % n], ring[(i + 1) % n]);
+            match neighbours.get(&point) {
+                Some(&(a, b)) => {
+                    let same = (a == previous && b == next) || (a == next && b == previous);
+                    if !same {
+                        junctions.insert(point);
+                    }
+                }
+                None => {
+                    neighbours.insert(point, (previous, next));
+                }
+            }
+        }
+    }
+
+    junctions
+}
+
+/// Splits a ring into arcs between consecutive junctions. A ring without
+/// junctions becomes one closed arc starting at its smallest point, so that
+/// identical rings, e.g. an enclave and the hole it fills, share the arc.
+fn cut_ring(ring: &[Point], junctions: &HashSet<Point>) -> Vec<Vec<Point>> {
+    let n = ring.len();
+    let start = match (0..n).find(|&i| junctions.contains(&ring[i])) {
+        Some(start) => start,
+        None => {
+            let start = (0..n).min_by_key(|&i| ring[i]).unwrap_or(0);
+            return vec![(0..=n).map(|k| ring[(start + k) % n]).collect()];
+        }
+    };
+
+    let mut arcs = Vec::new();
+    let mut current = vec![ring[start]];
+    for k in 1..=n {
+        let point = ring[(start + k) % n];
+        current.push(point);
+        if junctions.contains(&point) {
+            arcs.push(std::mem::replace(&mut current, vec![point]));
+        }
+    }
+
+    arcs
+}
+
+/// Arcs in insertion order; an arc that equals a stored one traversed
+/// backwards is referenced with the one's complement of its index.
+#[derive(Default)]
+struct ArcIndex {
+    arcs: Vec<Vec<Point>>,
+    positions: HashMap<Vec<Point>, usize>,
+}
+
+impl ArcIndex {
+    fn index(&mut self, arc: Vec<Point>) -> i64 {
+        if let Some(&index) = self.positions.get(&arc) {
+            return index as i64;
+        }
+
+        let reversed: Vec<Point> = arc.iter().rev().copied().collect();
+        if let Some(&index) = self.positions.get(&reversed) {
+            return !(index as i64);
+        }
+
+        let index = self.arcs.len();
+        self.positions.insert(arc.clone(), index);
+        self.arcs.push(arc);
+        index as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feature(id: &str, rings: Value) -> TopologyFeature {
+        TopologyFeature {
+            id: Some(id.to_string()),
+            properties: json!({}),
+            geometry: json!({ "type": "Polygon", "coordinates": rings }),
+        }
+    }
+
+    fn arcs_of(topology: &Value, index: usize) -> Vec<Vec<i64>> {
+        serde_json::from_value(topology["objects"]["countries"]["geometries"][index]["arcs"].clone()).unwrap()
+    }
+
+    /// Absolute grid positions of an arc, or of its reverse for `!i`.
+    fn decode_arc(topology: &Value, reference: i64) -> Vec<(i64, i64)> {
+        let index = if reference < 0 { !reference } else { reference } as usize;
+        let deltas: Vec<[i64; 2]> = serde_json::from_value(topology["arcs"][index].clone()).unwrap();
+        let mut position = (0, 0);
+        let mut points: Vec<(i64, i64)> = deltas
+            .iter()
+            .map(|[dx, dy]| {
+                position = (position.0 + dx, position.1 + dy);
+                position
+            })
+            .collect();
+        if reference < 0 {
+            points.reverse();
+        }
+
+        points
+    }
+
+    #[test]
+    fn neighbours_share_their_border_arc_in_opposite_directions() {
+        let left = feature("left", json!([[[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]]]));
+        let right = feature("right", json!([[[10, 0], [20, 0], [20, 10], [10, 10], [10, 0]]]));
+        let topology = build_topology("countries", &[left, right], 21);
+
+        let (left_arcs, right_arcs) = (arcs_of(&topology, 0).remove(0), arcs_of(&topology, 1).remove(0));
+        let shared: Vec<i64> = left_arcs.iter().copied().filter(|&i| right_arcs.contains(&!i)).collect();
+
+        assert_eq!(shared.len(), 1);
+        let border = decode_arc(&topology, shared[0]);
+        assert_eq!(border.first().map(|p| p.0), Some(10));
+        assert!(border.iter().all(|p| p.0 == 10));
+        assert_eq!(decode_arc(&topology, !shared[0]), border.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn rings_decode_to_closed_loops_on_the_grid() {
+        let square = feature("square", json!([[[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]]]));
+        let topology = build_topology("countries", &[square], 11);
+
+        assert_eq!(topology["transform"]["scale"], json!([1.0, 1.0]));
+        let ring: Vec<(i64, i64)> = arcs_of(&topology, 0)[0]
+            .iter()
+            .flat_map(|&reference| decode_arc(&topology, reference))
+            .collect();
+        assert_eq!(ring.first(), ring.last());
+        assert_eq!(ring.len(), 5);
+    }
+
+    #[test]
+    fn an_enclave_shares_the_arc_of_the_hole_it_fills() {
+        let outer = feature("outer", json!([
+            [[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]],
+            [[4, 4], [4, 6], [6, 6], [6, 4], [4, 4]]
+        ]));
+        let enclave = feature("enclave", json!([[[4, 4], [6, 4], [6, 6], [4, 6], [4, 4]]]));
+        let topology = build_topology("countries", &[outer, enclave], 11);
+
+        let hole = arcs_of(&topology, 0)[1].clone();
+        let enclave = arcs_of(&topology, 1)[0].clone();
+        assert_eq!(hole.len(), 1);
+        assert_eq!(enclave, vec![!hole[0]]);
+        assert_eq!(topology["arcs"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn quantization_is_bounded() {
+        assert_eq!(validate_quantization(None).unwrap(), DEFAULT_QUANTIZATION);
+        assert!(validate_quantization(Some(1)).is_err());
+        assert!(validate_quantization(Some(MAX_QUANTIZATION + 1)).is_err());
+    }
+}